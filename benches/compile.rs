@@ -0,0 +1,64 @@
+use std::io;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use hsml::{
+    compiler::{HsmlCompileOptions, compile, compile_to_writer},
+    parser::parse::parse,
+};
+
+/// Every element is nested in the previous one, next to a piped text.
+///
+/// The text is piped, as lines below a tag with inline text are not nested in it.
+fn deep_tree(depth: usize) -> String {
+    (0..depth)
+        .map(|level| {
+            let indentation = "  ".repeat(level);
+
+            format!(
+                "{indentation}div.level-{level}(data-level=\"{level}\")\n{indentation}  | Level {level}\n"
+            )
+        })
+        .collect()
+}
+
+/// All elements are children of the same list.
+fn wide_tree(width: usize) -> String {
+    let items: String = (0..width)
+        .map(|index| format!("  li.item(data-index=\"{index}\") Item {index}\n"))
+        .collect();
+
+    format!("ul.list\n{items}")
+}
+
+fn bench_compile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compile");
+
+    let trees = [
+        ("deep", 100, deep_tree(100)),
+        ("deep", 400, deep_tree(400)),
+        ("wide", 1_000, wide_tree(1_000)),
+        ("wide", 10_000, wide_tree(10_000)),
+    ];
+
+    for (name, size, source) in &trees {
+        let (_, ast) = parse(source).unwrap();
+        let options = HsmlCompileOptions::default();
+
+        group.throughput(Throughput::Bytes(
+            compile(&ast, &options).unwrap().len() as u64
+        ));
+        group.bench_with_input(BenchmarkId::new(*name, size), &ast, |b, ast| {
+            b.iter(|| compile(ast, &options))
+        });
+        group.bench_with_input(
+            BenchmarkId::new(format!("{name}_streaming"), size),
+            &ast,
+            |b, ast| b.iter(|| compile_to_writer(ast, &options, io::sink())),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compile);
+criterion_main!(benches);
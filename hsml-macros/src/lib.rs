@@ -0,0 +1,129 @@
+use std::{env, fs, path::PathBuf};
+
+use hsml::{
+    compiler::{HsmlCompileOptions, compile},
+    parser::parse::parse,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{LitStr, parse_macro_input};
+
+/// Parses and compiles the source, errors are rendered together with the offending line.
+///
+/// `line_offset` is added to the line numbers of errors, for sources that are embedded in another file.
+fn compile_source(source: &str, file: &str, line_offset: usize) -> Result<String, String> {
+    let (_, hsml_ast) = parse(source).map_err(|err| {
+        format!(
+            "{} [{}]\n{}",
+            err.message(),
+            err.code(),
+            err.snippet(file, source, line_offset).trim_end()
+        )
+    })?;
+
+    compile(&hsml_ast, &HsmlCompileOptions::default()).map_err(|err| {
+        // `compile_error!` adds its own prefix
+        err.to_string().trim_start_matches("error: ").to_string()
+    })
+}
+
+/// Compiles an HSML template to HTML at compile time and expands to a `&'static str`.
+///
+/// The template is a string literal, as the indentation would get lost in a token stream:
+///
+/// ```
+/// use hsml_macros::hsml;
+///
+/// const GREETING: &str = hsml! {r#"
+/// div.greeting
+///   p Hello
+/// "#};
+///
+/// assert_eq!(GREETING, r#"<div class="greeting"><p>Hello</p></div>"#);
+/// ```
+#[proc_macro]
+pub fn hsml(input: TokenStream) -> TokenStream {
+    let template = parse_macro_input!(input as LitStr);
+
+    // the first line of the template starts on the line of the literal
+    let span = template.span().unwrap();
+    let line_offset = span.line() - 1;
+
+    match compile_source(&template.value(), &span.file(), line_offset) {
+        Ok(html) => quote!(#html).into(),
+        Err(message) => syn::Error::new(template.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Compiles an HSML file to HTML at compile time and expands to a `&'static str`.
+///
+/// Like `include_str!`, the path is relative to the file the macro is called in:
+///
+/// ```ignore
+/// const INDEX: &str = hsml_macros::include_hsml!("templates/index.hsml");
+/// ```
+#[proc_macro]
+pub fn include_hsml(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    match include_file(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+fn include_file(path: &LitStr) -> Result<proc_macro2::TokenStream, String> {
+    let base_dir = path
+        .span()
+        .unwrap()
+        .local_file()
+        .and_then(|file| file.parent().map(PathBuf::from))
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_default();
+    // `include_bytes!` below would resolve a relative path against the calling file again
+    let file = fs::canonicalize(base_dir.join(path.value()))
+        .map_err(|err| format!("Unable to find {}: {}", path.value(), err))?;
+
+    let source = fs::read_to_string(&file)
+        .map_err(|err| format!("Unable to read {}: {}", file.display(), err))?;
+    let html = compile_source(&source, &file.display().to_string(), 0)?;
+
+    // including the bytes makes cargo recompile the crate when the file changes
+    let file = file.display().to_string();
+
+    Ok(quote! {
+        {
+            const _: &[u8] = include_bytes!(#file);
+            #html
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compile_source;
+
+    #[test]
+    fn it_should_compile_source() {
+        assert_eq!(
+            compile_source("div\n  p Hello\n", "index.hsml", 0),
+            Ok(String::from("<div><p>Hello</p></div>"))
+        );
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_describe_parse_error() {
+        assert_eq!(
+            compile_source("div\n  1p Hello\n", "src/main.rs", 4),
+            Err(String::from(
+                "Invalid tag name [E0001]\n --> src/main.rs:6:3\n  |\n6 |   1p Hello\n  |   ^^\n  = hint: Tag names must start with a letter and can only contain letters, digits, `-` and a `:` after a namespace prefix"
+            ))
+        );
+    }
+}
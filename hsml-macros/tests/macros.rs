@@ -0,0 +1,32 @@
+use hsml_macros::{hsml, include_hsml};
+
+#[test]
+fn it_should_compile_inline_template() {
+    let html = hsml! {r#"
+div#app.container
+  h1 Hello
+  a(href="/about") About
+"#};
+
+    assert_eq!(
+        html,
+        r#"<div id="app" class="container"><h1>Hello</h1><a href="/about">About</a></div>"#
+    );
+}
+
+#[test]
+fn it_should_compile_template_into_const() {
+    const HTML: &str = hsml!("p Hello\n");
+
+    assert_eq!(HTML, "<p>Hello</p>");
+}
+
+#[test]
+fn it_should_include_template() {
+    const HTML: &str = include_hsml!("fixtures/card.hsml");
+
+    assert_eq!(
+        HTML,
+        r#"<article class="card"><h2 class="card-title">Title</h2><p class="card-body">Body</p></article>"#
+    );
+}
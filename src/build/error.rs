@@ -0,0 +1,67 @@
+use std::{fmt, io, path::PathBuf};
+
+use crate::{compiler::error::CompileError, parser::error::HsmlParseError};
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// Reading the templates or writing the generated module failed.
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Parse {
+        file: PathBuf,
+        /// Content of the file, to show the offending line.
+        source: String,
+        error: HsmlParseError,
+    },
+    Compile {
+        file: PathBuf,
+        error: CompileError,
+    },
+    /// Two files map to the same constant, e.g. `about-us.hsml` and `about_us.hsml`.
+    DuplicateName {
+        name: String,
+        files: (PathBuf, PathBuf),
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Io { path, error } => {
+                write!(f, "error: Unable to access {}: {}", path.display(), error)
+            }
+            BuildError::Parse {
+                file,
+                source,
+                error,
+            } => write!(
+                f,
+                "error[{}]: {}\n{}",
+                error.code(),
+                error.message(),
+                error.snippet(&file.display().to_string(), source, 0)
+            ),
+            BuildError::Compile { file, error } => write!(f, "{} in {}", error, file.display()),
+            BuildError::DuplicateName { name, files } => write!(
+                f,
+                "error: {} and {} would both be compiled to the constant `{}`",
+                files.0.display(),
+                files.1.display(),
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Io { error, .. } => Some(error),
+            BuildError::Parse { error, .. } => Some(error),
+            BuildError::Compile { error, .. } => Some(error),
+            BuildError::DuplicateName { .. } => None,
+        }
+    }
+}
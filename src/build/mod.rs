@@ -0,0 +1,278 @@
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    compiler::{HsmlCompileOptions, HsmlDebugLocationOptions, compile},
+    parser::parse::parse,
+};
+
+use self::error::BuildError;
+
+pub mod error;
+
+/// Returns the `.hsml` files in the directory and all of its subdirectories, sorted by path.
+pub fn find_hsml_files(dir: &Path) -> Result<Vec<PathBuf>, BuildError> {
+    let mut files = vec![];
+
+    collect_hsml_files(dir, &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
+
+fn collect_hsml_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), BuildError> {
+    let io_error = |error| BuildError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+
+        if path.is_dir() {
+            collect_hsml_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "hsml") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns a path like `pages/about-us.hsml` into the constant name `PAGES_ABOUT_US`.
+fn const_name(path: &Path) -> String {
+    let path = path.with_extension("");
+    let components: Vec<_> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+
+    let mut name: String = components
+        .join("_")
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// Compiles every `.hsml` file in the directory and returns the source of a Rust module with one `pub const` per file.
+pub fn generate_templates(dir: &Path, options: &HsmlCompileOptions) -> Result<String, BuildError> {
+    let mut module = String::from("// @generated by hsml, do not edit\n");
+    let mut names: HashMap<String, PathBuf> = HashMap::new();
+
+    for file in find_hsml_files(dir)? {
+        let relative_path = file.strip_prefix(dir).unwrap_or(&file).to_path_buf();
+
+        let name = const_name(&relative_path);
+        if let Some(other_file) = names.insert(name.clone(), file.clone()) {
+            return Err(BuildError::DuplicateName {
+                name,
+                files: (other_file, file),
+            });
+        }
+
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(error) => return Err(BuildError::Io { path: file, error }),
+        };
+
+        let hsml_ast = match parse(&source) {
+            Ok((_, hsml_ast)) => hsml_ast,
+            Err(error) => {
+                return Err(BuildError::Parse {
+                    file,
+                    source,
+                    error,
+                });
+            }
+        };
+
+        // debug locations refer to the file that is compiled right now
+        let options = &HsmlCompileOptions {
+            debug_locations: options.debug_locations.as_ref().map(|debug_locations| {
+                HsmlDebugLocationOptions {
+                    file: relative_path.display().to_string(),
+                    ..debug_locations.clone()
+                }
+            }),
+            ..options.clone()
+        };
+
+        let html = match compile(&hsml_ast, options) {
+            Ok(html) => html,
+            Err(error) => return Err(BuildError::Compile { file, error }),
+        };
+
+        // the debug representation of a string is a valid Rust string literal
+        write!(
+            module,
+            "\n/// Compiled from `{}`.\npub const {}: &str = {:?};\n",
+            relative_path.display(),
+            name,
+            html
+        )
+        .expect("Unable to write module");
+    }
+
+    Ok(module)
+}
+
+/// Compiles the templates in `dir` into a Rust module at `out_file`, meant to be called from a build script.
+///
+/// ```ignore
+/// // build.rs
+/// let out_file = Path::new(&env::var("OUT_DIR").unwrap()).join("templates.rs");
+/// hsml::build::compile_templates("templates", &out_file, &HsmlCompileOptions::default()).unwrap();
+///
+/// // src/main.rs
+/// mod templates {
+///     include!(concat!(env!("OUT_DIR"), "/templates.rs"));
+/// }
+/// ```
+pub fn compile_templates(
+    dir: impl AsRef<Path>,
+    out_file: impl AsRef<Path>,
+    options: &HsmlCompileOptions,
+) -> Result<(), BuildError> {
+    let dir = dir.as_ref();
+    let out_file = out_file.as_ref();
+
+    // cargo scans the whole directory, so added and removed templates are picked up as well
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let module = generate_templates(dir, options)?;
+
+    fs::write(out_file, module).map_err(|error| BuildError::Io {
+        path: out_file.to_path_buf(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+    };
+
+    use crate::{
+        build::{const_name, error::BuildError, find_hsml_files, generate_templates},
+        compiler::HsmlCompileOptions,
+    };
+
+    /// Creates a fresh directory with the given templates.
+    fn template_dir(name: &str, templates: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("hsml-build-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        for (path, content) in templates {
+            let file = dir.join(path);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, content).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn it_should_build_const_name() {
+        assert_eq!(const_name(Path::new("index.hsml")), "INDEX");
+        assert_eq!(
+            const_name(Path::new("pages/about-us.hsml")),
+            "PAGES_ABOUT_US"
+        );
+        assert_eq!(const_name(Path::new("404.hsml")), "_404");
+    }
+
+    #[test]
+    fn it_should_find_hsml_files() {
+        let dir = template_dir(
+            "find",
+            &[
+                ("b.hsml", "p b\n"),
+                ("a/c.hsml", "p c\n"),
+                ("notes.md", "# Notes\n"),
+            ],
+        );
+
+        assert_eq!(
+            find_hsml_files(&dir).unwrap(),
+            vec![dir.join("a/c.hsml"), dir.join("b.hsml")]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn it_should_generate_templates() {
+        let dir = template_dir(
+            "generate",
+            &[
+                ("index.hsml", "h1 Hello \"World\"\n"),
+                ("partials/nav-bar.hsml", "nav\n  a(href=\"/\") Home\n"),
+            ],
+        );
+
+        let module = generate_templates(&dir, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            module,
+            r#"// @generated by hsml, do not edit
+
+/// Compiled from `index.hsml`.
+pub const INDEX: &str = "<h1>Hello \"World\"</h1>";
+
+/// Compiled from `partials/nav-bar.hsml`.
+pub const PARTIALS_NAV_BAR: &str = "<nav><a href=\"/\">Home</a></nav>";
+"#
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_reject_duplicate_names() {
+        let dir = template_dir(
+            "duplicate",
+            &[("about-us.hsml", "p a\n"), ("about_us.hsml", "p b\n")],
+        );
+
+        let err = generate_templates(&dir, &HsmlCompileOptions::default()).unwrap_err();
+
+        assert!(matches!(
+            &err,
+            BuildError::DuplicateName { name, .. } if name == "ABOUT_US"
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn it_should_return_parse_error_with_file() {
+        let dir = template_dir("parse", &[("index.hsml", "1div\n")]);
+
+        let err = generate_templates(&dir, &HsmlCompileOptions::default()).unwrap_err();
+
+        assert!(matches!(&err, BuildError::Parse { file, .. } if file == &dir.join("index.hsml")));
+        assert!(
+            err.to_string()
+                .starts_with("error[E0001]: Invalid tag name\n")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,5 +1,126 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::cli::print_parse_error;
+
 use clap::ArgMatches;
+use hsml::{
+    linter::{HsmlLintOptions, Severity, lint},
+    parser::parse::parse_recovering,
+};
+
+const DEFAULT_CONFIG_FILE: &str = ".hsmlrc.json";
+
+pub fn exec_check(matches: &ArgMatches) -> Result<(), &str> {
+    let path = matches.get_one::<PathBuf>("path");
+
+    let fallback_path = env::current_dir().expect("Unable to get current directory");
+    let path = path.unwrap_or(&fallback_path);
+
+    let mut options = load_options(matches.get_one::<PathBuf>("config"))?;
+
+    // rules passed on the command line override the config file
+    if let Some(rules) = matches.get_many::<String>("rule") {
+        for rule in rules {
+            let (id, severity) = rule
+                .split_once('=')
+                .ok_or("Rules must be given as <RULE>=<off|warning|error>")?;
+
+            let severity = match severity {
+                "off" => Severity::Off,
+                "warning" => Severity::Warning,
+                "error" => Severity::Error,
+                _ => return Err("Rule severity must be one of off, warning or error"),
+            };
+
+            options.rules.insert(id.to_string(), severity);
+        }
+    }
+
+    if let Some(id) = options.unknown_rule() {
+        eprintln!("Unknown rule: {}", id);
+        return Err("Configuration contains an unknown rule");
+    }
+
+    let has_errors = if path.is_dir() {
+        check_hsml_files_in_dir(path, &options)
+    } else if path.is_file() {
+        check_file(path, &options)
+    } else {
+        return Err("Path must be a file or directory");
+    };
+
+    if has_errors {
+        return Err("Found errors in .hsml files");
+    }
+
+    Ok(())
+}
+
+fn load_options(config_file: Option<&PathBuf>) -> Result<HsmlLintOptions, &'static str> {
+    let config_file = match config_file {
+        Some(config_file) => config_file.clone(),
+        None => {
+            let default_config_file = PathBuf::from(DEFAULT_CONFIG_FILE);
+
+            // the default config file is optional
+            if !default_config_file.is_file() {
+                return Ok(HsmlLintOptions::default());
+            }
+
+            default_config_file
+        }
+    };
+
+    let content = fs::read_to_string(config_file).map_err(|_| "Unable to read config file")?;
+
+    serde_json::from_str(&content).map_err(|_| "Unable to parse config file")
+}
+
+/// Checks the given file, prints all findings and returns whether there were any errors.
+fn check_file(file: &PathBuf, options: &HsmlLintOptions) -> bool {
+    let content = fs::read_to_string(file).expect("Unable to read file");
+
+    // parse the file, but keep going after errors so all of them are reported at once
+    let (hsml_ast, errors) = parse_recovering(&content);
+
+    for err in &errors {
+        print_parse_error(file, &content, err);
+    }
+
+    let diagnostics = lint(&content, &hsml_ast, options);
+
+    for diagnostic in &diagnostics {
+        eprintln!(
+            "{}:{}:{}: {}[{}]: {}",
+            file.display(),
+            diagnostic.span.start.line,
+            diagnostic.span.start.column,
+            diagnostic.severity,
+            diagnostic.rule,
+            diagnostic.message
+        );
+    }
+
+    !errors.is_empty()
+        || diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+}
+
+fn check_hsml_files_in_dir(dir: &PathBuf, options: &HsmlLintOptions) -> bool {
+    // check all hsml files in the directory and call this function recursively on all subdirectories
+    let mut has_errors = false;
+
+    for entry in fs::read_dir(dir).expect("Unable to read directory") {
+        let entry = entry.expect("Unable to read directory entry");
+        let path = entry.path();
+
+        if path.is_dir() {
+            has_errors |= check_hsml_files_in_dir(&path, options);
+        } else if path.extension().is_some_and(|ext| ext == "hsml") {
+            has_errors |= check_file(&path, options);
+        }
+    }
 
-pub fn exec_check(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Check")
+    has_errors
 }
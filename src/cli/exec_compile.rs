@@ -1,8 +1,19 @@
-use std::{env, fs, path::PathBuf};
+use std::{
+    env,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::cli::print_parse_error;
 
 use clap::ArgMatches;
 use hsml::{
-    compiler::{HsmlCompileOptions, compile},
+    build::find_hsml_files,
+    compiler::{
+        CompileTarget, HsmlCompileOptions, HsmlDebugLocationOptions, HsmlDocumentOptions,
+        HsmlMinifyOptions, compile_to_writer, compile_with_source_map,
+    },
     parser::parse::parse,
 };
 
@@ -14,16 +25,66 @@ pub fn exec_compile(matches: &ArgMatches) -> Result<(), &str> {
     let fallback_path = env::current_dir().expect("Unable to get current directory");
     let path = path.unwrap_or(&fallback_path);
 
+    let target = match matches.get_one::<String>("target").map(String::as_str) {
+        Some("xml") => CompileTarget::Xml,
+        Some("jsx") => CompileTarget::Jsx,
+        Some("vue") => CompileTarget::VueRender,
+        _ => CompileTarget::Html,
+    };
+
+    let options = HsmlCompileOptions {
+        target,
+        self_close_components: matches.get_flag("self_close_components"),
+        pretty: matches.get_flag("pretty").then(|| String::from("  ")),
+        minify: matches.get_flag("minify").then(|| HsmlMinifyOptions {
+            omit_optional_end_tags: matches.get_flag("omit_optional_end_tags"),
+        }),
+        debug_locations: matches
+            .get_one::<String>("debug_locations")
+            .map(|attribute_name| HsmlDebugLocationOptions {
+                attribute_name: attribute_name.clone(),
+                ..Default::default()
+            }),
+        document: matches.get_flag("document").then(|| {
+            let urls = |id| {
+                matches
+                    .get_many::<String>(id)
+                    .map(|urls| urls.cloned().collect())
+                    .unwrap_or_default()
+            };
+            let default_document = HsmlDocumentOptions::default();
+
+            HsmlDocumentOptions {
+                lang: matches
+                    .get_one::<String>("lang")
+                    .cloned()
+                    .unwrap_or(default_document.lang),
+                title: matches.get_one::<String>("title").cloned(),
+                stylesheets: urls("stylesheet"),
+                scripts: urls("script"),
+                ..default_document
+            }
+        }),
+        ..Default::default()
+    };
+
+    let source_map = matches.get_flag("source_map");
+
     if path.is_dir() {
-        compile_hsml_files_in_dir(path)
+        compile_hsml_files_in_dir(path, &options, source_map)
     } else if path.is_file() {
-        compile_file(path, out)
+        compile_file(path, out, &options, source_map)
     } else {
         Err("Path must be a file or directory")
     }
 }
 
-fn compile_file(file: &PathBuf, out_file: Option<&PathBuf>) -> Result<(), &'static str> {
+fn compile_file(
+    file: &PathBuf,
+    out_file: Option<&PathBuf>,
+    options: &HsmlCompileOptions,
+    source_map: bool,
+) -> Result<(), &'static str> {
     // check that file exists
     if !file.exists() {
         return Err("File does not exist");
@@ -44,41 +105,105 @@ fn compile_file(file: &PathBuf, out_file: Option<&PathBuf>) -> Result<(), &'stat
     // check that file exists and read it
     let content = fs::read_to_string(file).expect("Unable to read file");
 
-    let fallback_out_file = file.with_extension("html");
+    // debug locations refer to the file that is compiled right now
+    let options = &HsmlCompileOptions {
+        debug_locations: options.debug_locations.as_ref().map(|debug_locations| {
+            HsmlDebugLocationOptions {
+                file: file.display().to_string(),
+                ..debug_locations.clone()
+            }
+        }),
+        ..options.clone()
+    };
+
+    let fallback_out_file = file.with_extension(match options.target {
+        CompileTarget::Jsx => "jsx",
+        CompileTarget::VueRender => "js",
+        CompileTarget::Html | CompileTarget::Xml => "html",
+    });
     let out_file = out_file.unwrap_or(&fallback_out_file);
 
     // parse the file
-    let hsml_ast = if let Ok((_, hsml_ast)) = parse(&content) {
-        hsml_ast
-    } else {
-        return Err("Unable to parse file");
+    let hsml_ast = match parse(&content) {
+        Ok((_, hsml_ast)) => hsml_ast,
+        Err(err) => {
+            print_parse_error(file, &content, &err);
+            return Err("Aborting due to previous error");
+        }
     };
 
     // compile the AST
-    let html_content = compile(&hsml_ast, &HsmlCompileOptions::default());
+    if source_map {
+        let map_file = PathBuf::from(format!("{}.map", out_file.display()));
+
+        // the map lives next to the compiled file, so the source is referenced relative to it
+        let file_name = |path: &PathBuf| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let source = if file.parent() == out_file.parent() {
+            file_name(file)
+        } else {
+            fs::canonicalize(file)
+                .unwrap_or_else(|_| file.clone())
+                .display()
+                .to_string()
+        };
+
+        let (html_content, source_map) =
+            match compile_with_source_map(&hsml_ast, options, &file_name(out_file), &source) {
+                Ok(result) => result,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Err("Aborting due to previous error");
+                }
+            };
+
+        fs::write(&map_file, source_map.to_json()).expect("Unable to write file");
+        println!("Source map written to {}", map_file.display());
+
+        fs::write(out_file, html_content).expect("Unable to write file");
+    } else {
+        // stream the HTML into a temporary file instead of building it in memory first,
+        // it replaces the output only on success so a failed build keeps the previous one
+        let temp_file = PathBuf::from(format!("{}.tmp", out_file.display()));
+        let mut writer = BufWriter::new(File::create(&temp_file).expect("Unable to create file"));
+
+        let result =
+            compile_to_writer(&hsml_ast, options, &mut writer).and_then(|_| Ok(writer.flush()?));
+        drop(writer);
+
+        if let Err(err) = result {
+            fs::remove_file(&temp_file).ok();
+            eprintln!("{}", err);
+            return Err("Aborting due to previous error");
+        }
 
-    fs::write(out_file, html_content).expect("Unable to write file");
+        fs::rename(&temp_file, out_file).expect("Unable to write file");
+    }
 
     println!(
-        "Compiled HTML written to {} successfully",
+        "Compiled output written to {} successfully",
         out_file.display()
     );
 
     Ok(())
 }
 
-fn compile_hsml_files_in_dir(dir: &PathBuf) -> Result<(), &'static str> {
-    // compile all hsml files in the directory and call this function recursively on all subdirectories
-    // if there is an error, ignore it and continue
-    for entry in fs::read_dir(dir).expect("Unable to read directory") {
-        let entry = entry.expect("Unable to read directory entry");
-        let path = entry.path();
-
-        if path.is_dir() {
-            compile_hsml_files_in_dir(&path).ok();
-        } else if path.is_file() {
-            compile_file(&path, None).ok();
-        }
+fn compile_hsml_files_in_dir(
+    dir: &Path,
+    options: &HsmlCompileOptions,
+    source_map: bool,
+) -> Result<(), &'static str> {
+    let files = find_hsml_files(dir).map_err(|err| {
+        eprintln!("{}", err);
+        "Aborting due to previous error"
+    })?;
+
+    // compile all hsml files, if there is an error, ignore it and continue
+    for file in files {
+        compile_file(&file, None, options, source_map).ok();
     }
 
     Ok(())
@@ -1,5 +1,98 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::cli::print_parse_error;
+
 use clap::ArgMatches;
+use hsml::{
+    formatter::{HsmlFormatOptions, format},
+    parser::parse::parse,
+};
+
+pub fn exec_format(matches: &ArgMatches) -> Result<(), &str> {
+    let path = matches.get_one::<PathBuf>("path");
+    let check = matches.get_flag("check");
+
+    let fallback_path = env::current_dir().expect("Unable to get current directory");
+    let path = path.unwrap_or(&fallback_path);
+
+    let is_formatted = if path.is_dir() {
+        format_hsml_files_in_dir(path, check)?
+    } else if path.is_file() {
+        format_file(path, check)?
+    } else {
+        return Err("Path must be a file or directory");
+    };
+
+    if check && !is_formatted {
+        return Err("Some files are not formatted, run `hsml fmt` to format them");
+    }
+
+    Ok(())
+}
+
+/// Formats the given file and returns whether it was already formatted.
+///
+/// In check mode the file is never written.
+fn format_file(file: &PathBuf, check: bool) -> Result<bool, &'static str> {
+    // check that file ends with .hsml
+    file.extension()
+        .filter(|&ext| ext == "hsml")
+        .ok_or("File must have .hsml extension")?;
+
+    let content = fs::read_to_string(file).expect("Unable to read file");
+
+    // parse the file
+    let hsml_ast = match parse(&content) {
+        Ok((_, hsml_ast)) => hsml_ast,
+        Err(err) => {
+            print_parse_error(file, &content, &err);
+            return Err("Unable to format file with parse errors");
+        }
+    };
+
+    let formatted_content = match format(&hsml_ast, &HsmlFormatOptions::default()) {
+        Ok(formatted_content) => formatted_content,
+        Err(err) => {
+            eprintln!("{}", err);
+            return Err("Unable to format file");
+        }
+    };
+
+    if formatted_content == content {
+        return Ok(true);
+    }
+
+    if check {
+        println!("Would reformat {}", file.display());
+    } else {
+        fs::write(file, formatted_content).expect("Unable to write file");
+        println!("Formatted {}", file.display());
+    }
+
+    Ok(false)
+}
+
+fn format_hsml_files_in_dir(dir: &PathBuf, check: bool) -> Result<bool, &'static str> {
+    // format all hsml files in the directory and call this function recursively on all subdirectories
+    let mut is_formatted = true;
+
+    for entry in fs::read_dir(dir).expect("Unable to read directory") {
+        let entry = entry.expect("Unable to read directory entry");
+        let path = entry.path();
+
+        if path.is_dir() {
+            is_formatted &= format_hsml_files_in_dir(&path, check)?;
+        } else if path.extension().is_some_and(|ext| ext == "hsml") {
+            match format_file(&path, check) {
+                Ok(is_file_formatted) => is_formatted &= is_file_formatted,
+                Err(err) => {
+                    // a file that cannot be parsed can also not be formatted
+                    eprintln!("{}: {}", path.display(), err);
+                    is_formatted = false;
+                }
+            }
+        }
+    }
 
-pub fn exec_format(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Format");
+    Ok(is_formatted)
 }
@@ -1,5 +1,39 @@
+use std::{fs, path::PathBuf};
+
+use crate::cli::print_parse_error;
+
 use clap::ArgMatches;
+use hsml::parser::{json::to_json, parse::parse};
+
+pub fn exec_parse(matches: &ArgMatches) -> Result<(), &str> {
+    let file = matches
+        .get_one::<PathBuf>("path")
+        .ok_or("Path to .hsml file is required")?;
+    let pretty = matches.get_flag("pretty");
+
+    // check that file is a file
+    if !file.is_file() {
+        return Err("Given file must be a file");
+    }
+
+    // check that file ends with .hsml
+    file.extension()
+        .filter(|&ext| ext == "hsml")
+        .ok_or("File must have .hsml extension")?;
+
+    let content = fs::read_to_string(file).expect("Unable to read file");
+
+    // parse the file
+    let hsml_ast = match parse(&content) {
+        Ok((_, hsml_ast)) => hsml_ast,
+        Err(err) => {
+            print_parse_error(file, &content, &err);
+            return Err("Aborting due to previous error");
+        }
+    };
+
+    // only the JSON document is written to stdout, so it can be piped into other tools
+    println!("{}", to_json(&hsml_ast, pretty));
 
-pub fn exec_parse(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Parse");
+    Ok(())
 }
@@ -1,6 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::{Command, arg, command, value_parser};
+use clap::{ArgAction, Command, arg, command, value_parser};
+use hsml::parser::error::HsmlParseError;
 
 pub mod exec_check;
 pub mod exec_compile;
@@ -21,12 +22,74 @@ pub fn cli() -> Command {
                 .arg(
                     arg!(output: -o --out <OUTPUT> "Output file or directory")
                         .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(target: --target <TARGET> "Output dialect")
+                        .value_parser(["html", "xml", "jsx", "vue"])
+                        .default_value("html"),
+                )
+                .arg(arg!(pretty: --pretty "Pretty-print the HTML output where whitespace is not significant"))
+                .arg(arg!(self_close_components: --"self-close-components" "Write empty component tags like `RouterLink` as `<RouterLink/>`"))
+                .arg(arg!(minify: --minify "Minify the output, e.g. drop optional quotes and strip comments"))
+                .arg(
+                    arg!(omit_optional_end_tags: --"omit-optional-end-tags" "Leave out optional end tags like `</li>` in minified output")
+                        .requires("minify"),
+                )
+                .arg(arg!(source_map: --"source-map" "Write a source map next to each compiled file, e.g. `foo.html.map`"))
+                .arg(
+                    arg!(debug_locations: --"debug-locations" [ATTRIBUTE] "Add the source location of each element as attribute")
+                        .num_args(0..=1)
+                        .default_missing_value("data-hsml-loc"),
+                )
+                .arg(arg!(document: --document "Wrap the output in a complete document with doctype, head and body"))
+                .arg(arg!(lang: --lang <LANG> "Language of the document").requires("document"))
+                .arg(arg!(title: --title <TITLE> "Title of the document").requires("document"))
+                .arg(
+                    arg!(stylesheet: --stylesheet <URL> "Link a stylesheet in the head of the document")
+                        .action(ArgAction::Append)
+                        .requires("document"),
+                )
+                .arg(
+                    arg!(script: --script <URL> "Load a script in the head of the document")
+                        .action(ArgAction::Append)
+                        .requires("document"),
                 ),
         )
         .subcommand(
             Command::new("parse")
-                .about("Parse given .hsml file and print the AST to stdout as JSON"),
+                .about("Parse given .hsml file and print the AST to stdout as JSON")
+                .arg(arg!(path: <PATH> "Path to .hsml file").value_parser(value_parser!(PathBuf)))
+                .arg(arg!(pretty: --pretty "Pretty-print the JSON output")),
         )
-        .subcommand(Command::new("fmt").about("Format given .hsml file or directory"))
-        .subcommand(Command::new("check").about("Check given .hsml file or directory"))
+        .subcommand(
+            Command::new("fmt")
+                .about("Format given .hsml file or directory")
+                .arg(
+                    arg!(path: [PATH] "Path to .hsml file or directory containing .hsml files")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(check: --check "Exit with an error if any file is not formatted instead of writing it")),
+        )
+        .subcommand(
+            Command::new("check")
+                .about("Check given .hsml file or directory")
+                .arg(
+                    arg!(path: [PATH] "Path to .hsml file or directory containing .hsml files")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(config: -c --config <CONFIG> "Path to a JSON config file (defaults to .hsmlrc.json)")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(rule: -r --rule <RULE> "Override the severity of a rule, e.g. empty-class=off")
+                        .action(ArgAction::Append),
+                ),
+        )
+}
+
+/// Prints a parse error to stderr together with the offending source line.
+pub fn print_parse_error(file: &Path, source: &str, err: &HsmlParseError) {
+    eprintln!("error[{}]: {}", err.code(), err.message());
+    eprint!("{}", err.snippet(&file.display().to_string(), source, 0));
 }
@@ -0,0 +1,419 @@
+use std::fmt;
+
+use crate::{
+    compiler::{
+        CompileContext, CompileTarget, HsmlCompileOptions, HsmlDocumentOptions, Output,
+        compile_attribute_value, compile_attributes, compile_doctype_node, compile_nodes,
+        error::CompileError, html::is_void_element, is_block_node, is_output_node,
+    },
+    parser::{
+        HsmlNode, RootNode, attribute::node::AttributeNode, tag::node::TagNode,
+        text::node::TextNode,
+    },
+};
+
+/// Key and value of an attribute, `None` for boolean attributes.
+type Attribute<'a> = (&'a str, Option<&'a str>);
+
+/// Overrides the options with the metadata of the file, given as dev comments like `// @title About us`.
+fn read_metadata(nodes: &[&HsmlNode], document: &HsmlDocumentOptions) -> HsmlDocumentOptions {
+    let mut document = document.clone();
+
+    for node in nodes {
+        let HsmlNode::Comment(comment_node) = node else {
+            continue;
+        };
+
+        let Some((key, value)) = comment_node
+            .text
+            .trim()
+            .strip_prefix('@')
+            .filter(|_| comment_node.is_dev)
+            .and_then(|metadata| metadata.split_once(char::is_whitespace))
+        else {
+            continue;
+        };
+
+        let value = value.trim().to_string();
+
+        match key {
+            "lang" => document.lang = value,
+            "charset" => document.charset = value,
+            "viewport" => document.viewport = Some(value),
+            "title" => document.title = Some(value),
+            "stylesheet" => document.stylesheets.push(value),
+            "script" => document.scripts.push(value),
+            _ => {}
+        }
+    }
+
+    document
+}
+
+/// Returns the root nodes with the children of a root level `html` tag in its place.
+fn document_nodes(hsml_ast: &RootNode) -> Result<(Option<&TagNode>, Vec<&HsmlNode>), CompileError> {
+    let mut html = None;
+    let mut nodes = vec![];
+
+    for node in &hsml_ast.nodes {
+        match node {
+            HsmlNode::Tag(tag_node) if tag_node.tag == "html" => {
+                if html.is_some() {
+                    return Err(CompileError::DuplicateElement {
+                        tag: tag_node.tag.clone(),
+                        span: tag_node.span,
+                    });
+                }
+
+                html = Some(tag_node);
+                nodes.extend(tag_node.children.iter().flatten());
+            }
+            node => nodes.push(node),
+        }
+    }
+
+    Ok((html, nodes))
+}
+
+/// Returns the tag with the name, there can be at most one of them in a document.
+fn find_unique_tag<'a>(
+    nodes: &[&'a HsmlNode],
+    tag: &str,
+) -> Result<Option<&'a TagNode>, CompileError> {
+    let mut tag_nodes = nodes.iter().filter_map(|node| match node {
+        HsmlNode::Tag(tag_node) if tag_node.tag == tag => Some(tag_node),
+        _ => None,
+    });
+
+    let tag_node = tag_nodes.next();
+
+    if let Some(duplicate) = tag_nodes.next() {
+        return Err(CompileError::DuplicateElement {
+            tag: duplicate.tag.clone(),
+            span: duplicate.span,
+        });
+    }
+
+    Ok(tag_node)
+}
+
+/// Returns the value of the attribute, an empty string for boolean attributes.
+fn attribute_value<'a>(tag_node: &'a TagNode, key: &str) -> Option<&'a str> {
+    tag_node
+        .attributes
+        .iter()
+        .flatten()
+        .find_map(|node| match node {
+            HsmlNode::Attribute(AttributeNode {
+                key: attribute_key,
+                value,
+                ..
+            }) if attribute_key == key => Some(value.as_deref().unwrap_or("")),
+            _ => None,
+        })
+}
+
+/// Writes a line break and the indentation for pretty-printed output.
+fn write_line_break<W: fmt::Write>(
+    options: &HsmlCompileOptions,
+    depth: usize,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    if let Some(indent_string) = &options.pretty
+        && options.minify.is_none()
+    {
+        output.write_char('\n')?;
+
+        for _ in 0..depth {
+            output.write_str(indent_string)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_attribute<W: fmt::Write>(
+    key: &str,
+    value: Option<&str>,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    output.write_char(' ')?;
+    output.write_str(key)?;
+
+    match value {
+        Some(value) => {
+            output.write_char('=')?;
+            compile_attribute_value(value, false, options, output)
+        }
+        // XML has no boolean attributes
+        None if options.target == CompileTarget::Xml => {
+            output.write_char('=')?;
+            compile_attribute_value(key, false, options, output)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Writes an element that is not part of the source, like `<meta charset="utf-8">`.
+fn write_element<W: fmt::Write>(
+    tag: &str,
+    attributes: &[Attribute],
+    text: Option<&str>,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    output.write_char('<')?;
+    output.write_str(tag)?;
+
+    for (key, value) in attributes {
+        write_attribute(key, *value, options, output)?;
+    }
+
+    // same as void elements from the source
+    if text.is_none() && is_void_element(tag) {
+        return if options.minify.is_some() && options.target == CompileTarget::Html {
+            output.write_char('>')
+        } else {
+            output.write_str("/>")
+        };
+    }
+
+    output.write_char('>')?;
+
+    if let Some(text) = text {
+        options.write_text(text, output)?;
+    }
+
+    output.write_str("</")?;
+    output.write_str(tag)?;
+    output.write_char('>')
+}
+
+/// Writes the elements of the head that are not already in the head of the file.
+fn compile_head_elements<W: fmt::Write>(
+    document: &HsmlDocumentOptions,
+    head: Option<&TagNode>,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<bool, CompileError> {
+    let head_tags: Vec<&TagNode> = head
+        .and_then(|head| head.children.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|node| match node {
+            HsmlNode::Tag(tag_node) => Some(tag_node),
+            _ => None,
+        })
+        .collect();
+
+    let has_tag = |tag: &str, key: &str, value: Option<&str>| {
+        head_tags.iter().any(|tag_node| {
+            tag_node.tag == tag
+                && attribute_value(tag_node, key)
+                    .is_some_and(|attribute| value.is_none_or(|value| attribute == value))
+        })
+    };
+
+    let mut elements: Vec<(&str, Vec<Attribute>, Option<&str>)> = vec![];
+
+    if !has_tag("meta", "charset", None) {
+        elements.push(("meta", vec![("charset", Some(&document.charset))], None));
+    }
+
+    if let Some(viewport) = &document.viewport
+        && !has_tag("meta", "name", Some("viewport"))
+    {
+        elements.push((
+            "meta",
+            vec![("name", Some("viewport")), ("content", Some(viewport))],
+            None,
+        ));
+    }
+
+    if let Some(title) = &document.title
+        && !head_tags.iter().any(|tag_node| tag_node.tag == "title")
+    {
+        elements.push(("title", vec![], Some(title)));
+    }
+
+    for href in &document.stylesheets {
+        if !has_tag("link", "href", Some(href)) {
+            elements.push((
+                "link",
+                vec![("rel", Some("stylesheet")), ("href", Some(href))],
+                None,
+            ));
+        }
+    }
+
+    for src in &document.scripts {
+        if !has_tag("script", "src", Some(src)) {
+            elements.push(("script", vec![("src", Some(src)), ("defer", None)], None));
+        }
+    }
+
+    for (tag, attributes, text) in &elements {
+        write_line_break(options, 2, output)?;
+        write_element(tag, attributes, *text, options, output)?;
+    }
+
+    Ok(!elements.is_empty())
+}
+
+/// Writes the start tag of `html`, `head` or `body` with the given attributes
+/// followed by the attributes of the tag from the file, if there is one.
+fn compile_start_tag<W: fmt::Write>(
+    tag: &str,
+    attributes: &[Attribute],
+    tag_node: Option<&TagNode>,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    if let Some(tag_node) = tag_node {
+        output.map(tag_node.span.start);
+    }
+
+    output.write_char('<')?;
+    output.write_str(tag)?;
+
+    for (key, value) in attributes {
+        write_attribute(key, *value, options, output)?;
+    }
+
+    if let Some(tag_node) = tag_node {
+        compile_attributes(tag_node, false, options, output)?;
+    }
+
+    output.write_char('>')?;
+
+    Ok(())
+}
+
+/// Compiles the content of `head` or `body`, `has_lines` tells whether the element already contains lines of its own.
+fn compile_content<W: fmt::Write>(
+    nodes: &[&HsmlNode],
+    tag: &str,
+    has_lines: bool,
+    can_break: bool,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    let context = CompileContext {
+        depth: 2,
+        ..Default::default()
+    };
+
+    compile_nodes(nodes, Some(tag), options, context, can_break, output)?;
+
+    // otherwise the line break before the end tag is written along with the nodes
+    let has_node_lines = can_break
+        && nodes.iter().all(|node| is_block_node(node))
+        && nodes.iter().any(|node| is_output_node(node, options));
+
+    if has_lines && !has_node_lines {
+        write_line_break(options, 1, output)?;
+    }
+
+    Ok(())
+}
+
+/// Wraps the nodes in a complete document, nodes that are not in a root level `head` or `body` go into the body.
+///
+/// A root level `html` tag is unwrapped, its attributes and children are merged into the document.
+pub(super) fn compile_document<W: fmt::Write>(
+    hsml_ast: &RootNode,
+    document: &HsmlDocumentOptions,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    let (html, nodes) = document_nodes(hsml_ast)?;
+    let document = read_metadata(&nodes, document);
+
+    let head = find_unique_tag(&nodes, "head")?;
+    let body = find_unique_tag(&nodes, "body")?;
+
+    // a doctype in the file takes precedence
+    let doctype = nodes.iter().find_map(|node| match node {
+        HsmlNode::Doctype(doctype_node) => Some(doctype_node),
+        _ => None,
+    });
+
+    match doctype {
+        Some(doctype_node) => compile_doctype_node(doctype_node, options, output)?,
+        None => output.write_str("<!DOCTYPE html>")?,
+    }
+
+    write_line_break(options, 0, output)?;
+
+    let lang = match html.and_then(|html| attribute_value(html, "lang")) {
+        Some(_) => vec![],
+        None => vec![("lang", Some(document.lang.as_str()))],
+    };
+    compile_start_tag("html", &lang, html, options, output)?;
+
+    write_line_break(options, 1, output)?;
+    compile_start_tag("head", &[], head, options, output)?;
+
+    let has_head_elements = compile_head_elements(&document, head, options, output)?;
+
+    let head_nodes: Vec<&HsmlNode> = head
+        .and_then(|head| head.children.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .collect();
+    compile_content(
+        &head_nodes,
+        "head",
+        has_head_elements,
+        true,
+        options,
+        output,
+    )?;
+
+    output.write_str("</head>")?;
+
+    write_line_break(options, 1, output)?;
+    compile_start_tag("body", &[], body, options, output)?;
+
+    // text of the `html` tag can only be rendered in the body
+    let body_texts: Vec<&TextNode> = [html, body]
+        .into_iter()
+        .flatten()
+        .filter_map(|tag_node| tag_node.text.as_ref())
+        .collect();
+    for text_node in &body_texts {
+        output.map(text_node.span.start);
+        options.write_text(&text_node.text, output)?;
+    }
+
+    let body_nodes: Vec<&HsmlNode> = nodes
+        .iter()
+        .flat_map(|node| match node {
+            HsmlNode::Doctype(_) => vec![],
+            HsmlNode::Tag(tag_node) if tag_node.tag == "head" => vec![],
+            HsmlNode::Tag(tag_node) if tag_node.tag == "body" => {
+                tag_node.children.iter().flatten().collect()
+            }
+            node => vec![*node],
+        })
+        .collect();
+    compile_content(
+        &body_nodes,
+        "body",
+        false,
+        body_texts.is_empty(),
+        options,
+        output,
+    )?;
+
+    output.write_str("</body>")?;
+
+    write_line_break(options, 0, output)?;
+    output.write_str("</html>")?;
+
+    // like the output of regular pretty-printed documents
+    write_line_break(options, 0, output)?;
+
+    Ok(())
+}
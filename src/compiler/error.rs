@@ -0,0 +1,188 @@
+use std::{fmt, io};
+
+use crate::parser::{HsmlNode, span::Span};
+
+/// Where in the AST a node was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeContext {
+    Root,
+    Children,
+    Attributes,
+}
+
+#[derive(Debug)]
+pub enum CompileError {
+    /// A node that cannot appear at its position, e.g. a class node among the children of a tag.
+    ///
+    /// The parser never produces such an AST, but one that is built by hand or deserialized could.
+    UnexpectedNode {
+        /// The `type` of the node as in the AST JSON, e.g. `class`.
+        node: &'static str,
+        context: NodeContext,
+        /// `None` for nodes without a location like the root node.
+        span: Option<Span>,
+    },
+    /// A feature the target cannot express, e.g. a `v-if` directive in a Vue render function.
+    Unsupported {
+        /// Description of the feature, e.g. a "`v-if` directive".
+        feature: String,
+        /// Name of the target, e.g. `Vue render function`.
+        target: &'static str,
+        span: Span,
+    },
+    /// A void element like `br` or `img` with text or children, which HTML cannot express.
+    VoidElementContent { tag: String, span: Span },
+    /// A second `html`, `head` or `body` element in a document.
+    DuplicateElement { tag: String, span: Span },
+    /// A compile option the target cannot be combined with, e.g. the document option with JSX.
+    UnsupportedOption {
+        option: &'static str,
+        target: &'static str,
+    },
+    /// Writing into a `fmt::Write` failed.
+    Fmt(fmt::Error),
+    /// Writing into an `io::Write` failed, e.g. because the socket was closed.
+    Io(io::Error),
+}
+
+impl CompileError {
+    pub fn unexpected_node(node: &HsmlNode, context: NodeContext) -> Self {
+        CompileError::UnexpectedNode {
+            node: node.type_name(),
+            context,
+            span: node.span(),
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CompileError::UnexpectedNode { span, .. } => *span,
+            CompileError::Unsupported { span, .. }
+            | CompileError::VoidElementContent { span, .. }
+            | CompileError::DuplicateElement { span, .. } => Some(*span),
+            CompileError::UnsupportedOption { .. } | CompileError::Fmt(_) | CompileError::Io(_) => {
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for NodeContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NodeContext::Root => "at the root level",
+            NodeContext::Children => "in the children of a tag",
+            NodeContext::Attributes => "in the attributes of a tag",
+        })
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::UnexpectedNode {
+                node,
+                context,
+                span,
+            } => {
+                write!(f, "error: Unexpected {} node {}", node, context)?;
+
+                if let Some(span) = span {
+                    write!(f, " at {}:{}", span.start.line, span.start.column)?;
+                }
+
+                Ok(())
+            }
+            CompileError::Unsupported {
+                feature,
+                target,
+                span,
+            } => write!(
+                f,
+                "error: Unsupported {} in the {} target at {}:{}",
+                feature, target, span.start.line, span.start.column
+            ),
+            CompileError::VoidElementContent { tag, span } => write!(
+                f,
+                "error: Void element `{}` cannot have content at {}:{}",
+                tag, span.start.line, span.start.column
+            ),
+            CompileError::DuplicateElement { tag, span } => write!(
+                f,
+                "error: Duplicate `{}` element in the document at {}:{}",
+                tag, span.start.line, span.start.column
+            ),
+            CompileError::UnsupportedOption { option, target } => write!(
+                f,
+                "error: The {} option is not supported by the {} target",
+                option, target
+            ),
+            CompileError::Fmt(err) => write!(f, "error: Unable to write output: {}", err),
+            CompileError::Io(err) => write!(f, "error: Unable to write output: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CompileError::UnexpectedNode { .. }
+            | CompileError::Unsupported { .. }
+            | CompileError::VoidElementContent { .. }
+            | CompileError::DuplicateElement { .. }
+            | CompileError::UnsupportedOption { .. } => None,
+            CompileError::Fmt(err) => Some(err),
+            CompileError::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<fmt::Error> for CompileError {
+    fn from(err: fmt::Error) -> Self {
+        CompileError::Fmt(err)
+    }
+}
+
+impl From<io::Error> for CompileError {
+    fn from(err: io::Error) -> Self {
+        CompileError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compiler::error::{CompileError, NodeContext},
+        parser::{HsmlNode, RootNode, class::node::ClassNode, span::LineIndex},
+    };
+
+    #[test]
+    fn it_should_describe_unexpected_node() {
+        let line_index = LineIndex::new("div\n  .a\n");
+        let node = HsmlNode::Class(ClassNode {
+            name: String::from("a"),
+            span: line_index.span(6, 8),
+        });
+
+        let err = CompileError::unexpected_node(&node, NodeContext::Children);
+
+        assert_eq!(err.span(), Some(line_index.span(6, 8)));
+        assert_eq!(
+            err.to_string(),
+            "error: Unexpected class node in the children of a tag at 2:3"
+        );
+    }
+
+    #[test]
+    fn it_should_describe_unexpected_node_without_span() {
+        let node = HsmlNode::Root(RootNode { nodes: vec![] });
+
+        let err = CompileError::unexpected_node(&node, NodeContext::Attributes);
+
+        assert_eq!(err.span(), None);
+        assert_eq!(
+            err.to_string(),
+            "error: Unexpected root node in the attributes of a tag"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+
+/// Returns whether the value of the attribute is an expression of a framework like Vue or Angular,
+/// e.g. `:src`, `@click`, `#header`, `v-if`, `[value]`, `(click)` or `*ngIf`.
+pub fn is_binding_attribute(key: &str) -> bool {
+    key.starts_with([':', '@', '#', '[', '(', '*']) || key.starts_with("v-")
+}
+
+/// Returns the length of the character reference at the start of the input, e.g. `&amp;` or `&#x27;`.
+fn character_reference_len(input: &str) -> Option<usize> {
+    let rest = input.strip_prefix('&')?;
+
+    let (digits, prefix_len, is_digit): (&str, usize, fn(char) -> bool) =
+        if let Some(hex) = rest.strip_prefix("#x").or_else(|| rest.strip_prefix("#X")) {
+            (hex, 3, |c| c.is_ascii_hexdigit())
+        } else if let Some(decimal) = rest.strip_prefix('#') {
+            (decimal, 2, |c| c.is_ascii_digit())
+        } else {
+            (rest, 1, |c| c.is_ascii_alphanumeric())
+        };
+
+    let len = digits.find(|c: char| !is_digit(c)).unwrap_or(digits.len());
+
+    (len > 0 && digits[len..].starts_with(';')).then_some(prefix_len + len + 1)
+}
+
+/// Returns the length of the `{{ ... }}` interpolation at the start of the input.
+fn interpolation_len(input: &str) -> Option<usize> {
+    if !input.starts_with("{{") {
+        return None;
+    }
+
+    input.find("}}").map(|end| end + 2)
+}
+
+/// Writes the text and replaces the special chars, except for the parts at which `kept_len` returns a length.
+fn write_escaped<W: fmt::Write>(
+    text: &str,
+    writer: &mut W,
+    special_chars: &[char],
+    kept_len: impl Fn(&str) -> Option<usize>,
+    replacement: impl Fn(char) -> &'static str,
+) -> fmt::Result {
+    let mut rest = text;
+
+    while let Some(index) = rest.find(special_chars) {
+        writer.write_str(&rest[..index])?;
+        rest = &rest[index..];
+
+        if let Some(len) = kept_len(rest) {
+            writer.write_str(&rest[..len])?;
+            rest = &rest[len..];
+            continue;
+        }
+
+        // all special chars are ASCII
+        writer.write_str(replacement(char::from(rest.as_bytes()[0])))?;
+        rest = &rest[1..];
+    }
+
+    writer.write_str(rest)
+}
+
+/// Escapes `&`, `<` and `>`, but keeps character references and `{{ ... }}` interpolations as they are.
+pub fn escape_html_to<W: fmt::Write>(text: &str, writer: &mut W) -> fmt::Result {
+    write_escaped(
+        text,
+        writer,
+        &['&', '<', '>', '{'],
+        // interpolations are evaluated by the framework and must not be touched
+        |rest| interpolation_len(rest).or_else(|| character_reference_len(rest)),
+        |c| match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            // a brace that does not start an interpolation
+            _ => "{",
+        },
+    )
+}
+
+/// Escapes `&`, `<` and `>`, but keeps numeric character references and the five entities predefined by XML.
+pub fn escape_xml_to<W: fmt::Write>(text: &str, writer: &mut W) -> fmt::Result {
+    write_escaped(
+        text,
+        writer,
+        &['&', '<', '>'],
+        |rest| {
+            character_reference_len(rest).filter(|&len| {
+                rest[1..].starts_with('#')
+                    || ["amp", "lt", "gt", "quot", "apos"].contains(&&rest[1..len - 1])
+            })
+        },
+        |c| match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            _ => "&gt;",
+        },
+    )
+}
+
+/// Escapes `&`, `<`, `>`, `{` and `}`, but keeps character references, which JSX decodes like HTML.
+pub fn escape_jsx_to<W: fmt::Write>(text: &str, writer: &mut W) -> fmt::Result {
+    write_escaped(
+        text,
+        writer,
+        &['&', '<', '>', '{', '}'],
+        character_reference_len,
+        |c| match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            // braces would start an expression
+            '{' => "&#123;",
+            _ => "&#125;",
+        },
+    )
+}
+
+/// Same as [`escape_html_to`], but returns the escaped text.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    escape_html_to(text, &mut escaped).expect("Unable to write to string");
+
+    escaped
+}
+
+/// Same as [`escape_xml_to`], but returns the escaped text.
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    escape_xml_to(text, &mut escaped).expect("Unable to write to string");
+
+    escaped
+}
+
+/// Same as [`escape_jsx_to`], but returns the escaped text.
+pub fn escape_jsx(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    escape_jsx_to(text, &mut escaped).expect("Unable to write to string");
+
+    escaped
+}
+
+/// Replaces double quotes with `&quot;` while writing, for attribute values that contain both kinds of quotes.
+pub struct EscapeQuotes<'a, W: fmt::Write>(pub &'a mut W);
+
+impl<W: fmt::Write> fmt::Write for EscapeQuotes<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut parts = s.split('"');
+
+        if let Some(part) = parts.next() {
+            self.0.write_str(part)?;
+        }
+
+        for part in parts {
+            self.0.write_str("&quot;")?;
+            self.0.write_str(part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Makes the text safe to be used inside of `<!--` and `-->`.
+pub fn sanitize_comment(text: &str) -> String {
+    let mut sanitized = String::with_capacity(text.len());
+
+    // a comment must not start with `>` or `->`
+    if text.starts_with('>') || text.starts_with("->") {
+        sanitized.push(' ');
+    }
+
+    for c in text.chars() {
+        // `--` could end the comment, so separate each dash from the previous one
+        if c == '-' && sanitized.ends_with('-') {
+            sanitized.push(' ');
+        }
+
+        sanitized.push(c);
+    }
+
+    sanitized
+}
+
+/// Makes the text safe to be used inside of `{/*` and `*/}`.
+pub fn sanitize_jsx_comment(text: &str) -> String {
+    text.replace("*/", "* /")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::escape::{
+        EscapeQuotes, escape_html, escape_html_to, escape_jsx, escape_xml, is_binding_attribute,
+        sanitize_comment, sanitize_jsx_comment,
+    };
+
+    #[test]
+    fn it_should_escape_html() {
+        assert_eq!(
+            escape_html("a < b && c > d & e"),
+            "a &lt; b &amp;&amp; c &gt; d &amp; e"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_html_into_writer() {
+        let mut html = String::from("<p title=\"");
+
+        escape_html_to("a \"<b>\" & {{ c }}", &mut EscapeQuotes(&mut html)).unwrap();
+
+        assert_eq!(html, "<p title=\"a &quot;&lt;b&gt;&quot; &amp; {{ c }}");
+    }
+
+    #[test]
+    fn it_should_keep_character_references() {
+        assert_eq!(
+            escape_html("&nbsp;&#169;&#x1F600;&copy &#;"),
+            "&nbsp;&#169;&#x1F600;&amp;copy &amp;#;"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_interpolations() {
+        assert_eq!(
+            escape_html("{{ a < b && c }} < {{ d"),
+            "{{ a < b && c }} &lt; {{ d"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_xml() {
+        assert_eq!(
+            escape_xml("&nbsp;&amp;&#169;&apos; {{ a < b }}"),
+            "&amp;nbsp;&amp;&#169;&apos; {{ a &lt; b }}"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_jsx() {
+        assert_eq!(
+            escape_jsx("{{ a }} < b & &copy; {c}"),
+            "&#123;&#123; a &#125;&#125; &lt; b &amp; &copy; &#123;c&#125;"
+        );
+    }
+
+    #[test]
+    fn it_should_sanitize_jsx_comment() {
+        assert_eq!(sanitize_jsx_comment(" a */ b "), " a * / b ");
+    }
+
+    #[test]
+    fn it_should_sanitize_comment() {
+        assert_eq!(sanitize_comment(" a -- b ---> c"), " a - - b - - -> c");
+        assert_eq!(sanitize_comment("->"), " ->");
+        assert_eq!(sanitize_comment(">"), " >");
+    }
+
+    #[test]
+    fn it_should_detect_binding_attributes() {
+        for key in [
+            ":src", "@click", "#header", "v-if", "[value]", "(click)", "*ngIf",
+        ] {
+            assert!(is_binding_attribute(key), "{key}");
+        }
+
+        for key in ["src", "title", "data-v"] {
+            assert!(!is_binding_attribute(key), "{key}");
+        }
+    }
+}
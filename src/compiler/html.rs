@@ -0,0 +1,399 @@
+/// Elements that never have content or an end tag, see https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag)
+}
+
+/// Attributes whose presence alone means `true`, see https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "shadowrootclonable",
+    "shadowrootdelegatesfocus",
+    "shadowrootserializable",
+];
+
+pub fn is_boolean_attribute(key: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&key)
+}
+
+/// Elements that are not rendered inline, so whitespace around them is not visible.
+///
+/// Everything else, including custom elements and components, is treated as inline.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "base",
+    "blockquote",
+    "body",
+    "caption",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "menu",
+    "meta",
+    "nav",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "pre",
+    "script",
+    "search",
+    "section",
+    "source",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+];
+
+pub fn is_block_element(tag: &str) -> bool {
+    BLOCK_ELEMENTS.contains(&tag)
+}
+
+/// Returns whether whitespace inside of the element is rendered as it is.
+pub fn preserves_whitespace(tag: &str) -> bool {
+    matches!(tag, "pre" | "textarea" | "listing" | "plaintext")
+}
+
+/// Elements that close a `p` element whose end tag was left out.
+const P_CLOSING_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Returns whether the end tag of the element can be left out, see https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+///
+/// `next` is the tag name of the next sibling, an empty string if it is text and `None` if there is none.
+/// `parent` is the tag name of the parent element, `None` at the root level.
+pub fn can_omit_end_tag(tag: &str, next: Option<&str>, parent: Option<&str>) -> bool {
+    let is_last = next.is_none();
+
+    match tag {
+        "html" | "head" | "body" => true,
+        "li" => is_last || next == Some("li"),
+        "dt" => matches!(next, Some("dt" | "dd")),
+        "dd" => is_last || matches!(next, Some("dt" | "dd")),
+        "p" => match next {
+            Some(next) => P_CLOSING_ELEMENTS.contains(&next),
+            None => !matches!(
+                parent,
+                Some("a" | "audio" | "del" | "ins" | "map" | "noscript" | "video")
+            ),
+        },
+        "rt" | "rp" => is_last || matches!(next, Some("rt" | "rp")),
+        "optgroup" => is_last || next == Some("optgroup"),
+        "option" => is_last || matches!(next, Some("option" | "optgroup")),
+        "colgroup" | "caption" => next != Some(""),
+        "thead" => matches!(next, Some("tbody" | "tfoot")),
+        "tbody" => is_last || matches!(next, Some("tbody" | "tfoot")),
+        "tfoot" => is_last,
+        "tr" => is_last || next == Some("tr"),
+        "td" | "th" => is_last || matches!(next, Some("td" | "th")),
+        _ => false,
+    }
+}
+
+/// Returns whether the tag starts SVG or MathML content, in which empty elements may self-close.
+pub fn is_foreign_root(tag: &str) -> bool {
+    tag == "svg" || tag == "math"
+}
+
+/// Returns whether the children of the foreign element are HTML again.
+pub fn is_html_integration_point(tag: &str) -> bool {
+    tag == "foreignObject"
+}
+
+/// Returns whether the tag is a component of a framework like Vue, e.g. `RouterLink`.
+pub fn is_component(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Returns whether the name is a valid XML name with an optional namespace prefix, e.g. `xlink:href`.
+pub fn is_xml_name(name: &str) -> bool {
+    let is_name_part = |part: &str| {
+        part.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && part
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '_'))
+    };
+
+    match name.split_once(':') {
+        Some((prefix, local_name)) => is_name_part(prefix) && is_name_part(local_name),
+        None => is_name_part(name),
+    }
+}
+
+/// Names of HTML and SVG attributes that React expects in camelCase, see https://react.dev/reference/react-dom/components/common
+const REACT_PROP_NAMES: &[(&str, &str)] = &[
+    ("accept-charset", "acceptCharset"),
+    ("accesskey", "accessKey"),
+    ("allowfullscreen", "allowFullScreen"),
+    ("autocapitalize", "autoCapitalize"),
+    ("autocomplete", "autoComplete"),
+    ("autofocus", "autoFocus"),
+    ("autoplay", "autoPlay"),
+    ("cellpadding", "cellPadding"),
+    ("cellspacing", "cellSpacing"),
+    ("charset", "charSet"),
+    ("class", "className"),
+    ("clip-path", "clipPath"),
+    ("clip-rule", "clipRule"),
+    ("colspan", "colSpan"),
+    ("contenteditable", "contentEditable"),
+    ("crossorigin", "crossOrigin"),
+    ("datetime", "dateTime"),
+    ("enctype", "encType"),
+    ("enterkeyhint", "enterKeyHint"),
+    ("fetchpriority", "fetchPriority"),
+    ("fill-opacity", "fillOpacity"),
+    ("fill-rule", "fillRule"),
+    ("for", "htmlFor"),
+    ("formaction", "formAction"),
+    ("formenctype", "formEncType"),
+    ("formmethod", "formMethod"),
+    ("formnovalidate", "formNoValidate"),
+    ("formtarget", "formTarget"),
+    ("frameborder", "frameBorder"),
+    ("hreflang", "hrefLang"),
+    ("http-equiv", "httpEquiv"),
+    ("inputmode", "inputMode"),
+    ("itemid", "itemID"),
+    ("itemprop", "itemProp"),
+    ("itemref", "itemRef"),
+    ("itemscope", "itemScope"),
+    ("itemtype", "itemType"),
+    ("maxlength", "maxLength"),
+    ("minlength", "minLength"),
+    ("nomodule", "noModule"),
+    ("novalidate", "noValidate"),
+    ("playsinline", "playsInline"),
+    ("popovertarget", "popoverTarget"),
+    ("popovertargetaction", "popoverTargetAction"),
+    ("readonly", "readOnly"),
+    ("referrerpolicy", "referrerPolicy"),
+    ("rowspan", "rowSpan"),
+    ("spellcheck", "spellCheck"),
+    ("srcdoc", "srcDoc"),
+    ("srclang", "srcLang"),
+    ("srcset", "srcSet"),
+    ("stop-color", "stopColor"),
+    ("stroke-dasharray", "strokeDasharray"),
+    ("stroke-dashoffset", "strokeDashoffset"),
+    ("stroke-linecap", "strokeLinecap"),
+    ("stroke-linejoin", "strokeLinejoin"),
+    ("stroke-opacity", "strokeOpacity"),
+    ("stroke-width", "strokeWidth"),
+    ("tabindex", "tabIndex"),
+    ("usemap", "useMap"),
+    ("xlink:href", "xlinkHref"),
+    ("xml:lang", "xmlLang"),
+];
+
+/// Returns the name React uses for the attribute, e.g. `tabIndex` for `tabindex`.
+pub fn react_prop_name(key: &str) -> &str {
+    REACT_PROP_NAMES
+        .iter()
+        .find(|(attribute, _)| *attribute == key)
+        .map_or(key, |(_, prop)| prop)
+}
+
+/// Hyphenated SVG and MathML elements, these names cannot be used for custom elements.
+const RESERVED_ELEMENT_NAMES: &[&str] = &[
+    "annotation-xml",
+    "color-profile",
+    "font-face",
+    "font-face-src",
+    "font-face-uri",
+    "font-face-format",
+    "font-face-name",
+    "missing-glyph",
+];
+
+/// Returns whether the tag is a custom element or a kebab-case component, e.g. `router-link`.
+pub fn is_custom_element(tag: &str) -> bool {
+    tag.contains('-') && !RESERVED_ELEMENT_NAMES.contains(&tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::html::{
+        can_omit_end_tag, is_block_element, is_boolean_attribute, is_component, is_custom_element,
+        is_void_element, is_xml_name, preserves_whitespace, react_prop_name,
+    };
+
+    #[test]
+    fn it_should_detect_void_elements() {
+        assert!(is_void_element("img"));
+        assert!(is_void_element("br"));
+        assert!(!is_void_element("div"));
+        assert!(!is_void_element("slot"));
+    }
+
+    #[test]
+    fn it_should_detect_boolean_attributes() {
+        assert!(is_boolean_attribute("disabled"));
+        assert!(is_boolean_attribute("checked"));
+        assert!(!is_boolean_attribute("name"));
+        assert!(!is_boolean_attribute("value"));
+    }
+
+    #[test]
+    fn it_should_detect_block_elements() {
+        assert!(is_block_element("div"));
+        assert!(is_block_element("li"));
+        assert!(!is_block_element("span"));
+        assert!(!is_block_element("my-icon"));
+        assert!(!is_block_element("RouterLink"));
+    }
+
+    #[test]
+    fn it_should_detect_whitespace_preserving_elements() {
+        assert!(preserves_whitespace("pre"));
+        assert!(preserves_whitespace("textarea"));
+        assert!(!preserves_whitespace("div"));
+    }
+
+    #[test]
+    fn it_should_detect_optional_end_tags() {
+        assert!(can_omit_end_tag("li", None, Some("ul")));
+        assert!(can_omit_end_tag("li", Some("li"), Some("ul")));
+        assert!(!can_omit_end_tag("li", Some(""), Some("ul")));
+        assert!(can_omit_end_tag("p", Some("div"), Some("div")));
+        assert!(!can_omit_end_tag("p", Some("span"), Some("div")));
+        assert!(can_omit_end_tag("p", None, Some("div")));
+        assert!(!can_omit_end_tag("p", None, Some("a")));
+        assert!(!can_omit_end_tag("dt", None, Some("dl")));
+        assert!(can_omit_end_tag("td", Some("th"), Some("tr")));
+        assert!(!can_omit_end_tag("div", None, None));
+    }
+
+    #[test]
+    fn it_should_detect_components() {
+        assert!(is_component("RouterLink"));
+        assert!(!is_component("router-link"));
+        assert!(!is_component("div"));
+    }
+
+    #[test]
+    fn it_should_detect_custom_elements() {
+        assert!(is_custom_element("router-link"));
+        assert!(is_custom_element("my-element"));
+        assert!(!is_custom_element("div"));
+        assert!(!is_custom_element("font-face"));
+    }
+
+    #[test]
+    fn it_should_detect_xml_names() {
+        assert!(is_xml_name("href"));
+        assert!(is_xml_name("xlink:href"));
+        assert!(is_xml_name("data-v.1_x"));
+        assert!(!is_xml_name(":class"));
+        assert!(!is_xml_name("@click"));
+        assert!(!is_xml_name("[value]"));
+        assert!(!is_xml_name("#slot"));
+        assert!(!is_xml_name("a:b:c"));
+    }
+
+    #[test]
+    fn it_should_map_react_prop_names() {
+        assert_eq!(react_prop_name("class"), "className");
+        assert_eq!(react_prop_name("tabindex"), "tabIndex");
+        assert_eq!(react_prop_name("stroke-width"), "strokeWidth");
+        assert_eq!(react_prop_name("aria-label"), "aria-label");
+        assert_eq!(react_prop_name("href"), "href");
+    }
+}
@@ -0,0 +1,61 @@
+/// Replaces whitespace containing line breaks with a single space, e.g. in multiline `:class` objects.
+pub fn collapse_line_breaks(value: &str) -> String {
+    let mut collapsed = String::with_capacity(value.len());
+    let mut whitespace = String::new();
+
+    for c in value.chars() {
+        if c.is_whitespace() {
+            whitespace.push(c);
+            continue;
+        }
+
+        if whitespace.contains('\n') {
+            // leading line breaks are dropped completely
+            if !collapsed.is_empty() {
+                collapsed.push(' ');
+            }
+        } else {
+            collapsed.push_str(&whitespace);
+        }
+
+        whitespace.clear();
+        collapsed.push(c);
+    }
+
+    // trailing line breaks are dropped completely
+    if !whitespace.contains('\n') {
+        collapsed.push_str(&whitespace);
+    }
+
+    collapsed
+}
+
+/// Returns whether the value can be written without quotes, see https://html.spec.whatwg.org/multipage/syntax.html#unquoted
+pub fn can_be_unquoted(value: &str) -> bool {
+    !value.is_empty() && !value.contains(|c: char| c.is_ascii_whitespace() || "\"'=<>`".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::minify::{can_be_unquoted, collapse_line_breaks};
+
+    #[test]
+    fn it_should_collapse_line_breaks() {
+        assert_eq!(
+            collapse_line_breaks("{\n    'a  b': isA,\n    'c': isC,\n  }"),
+            "{ 'a  b': isA, 'c': isC, }"
+        );
+        assert_eq!(collapse_line_breaks("\n  a\n"), "a");
+        assert_eq!(collapse_line_breaks(" a  b "), " a  b ");
+    }
+
+    #[test]
+    fn it_should_detect_values_that_can_be_unquoted() {
+        assert!(can_be_unquoted("card"));
+        assert!(can_be_unquoted("/a.png?v&amp;w"));
+        assert!(!can_be_unquoted(""));
+        assert!(!can_be_unquoted("a b"));
+        assert!(!can_be_unquoted("a=b"));
+        assert!(!can_be_unquoted("it's"));
+    }
+}
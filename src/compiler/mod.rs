@@ -1,203 +1,1955 @@
+use std::{
+    borrow::{Borrow, Cow},
+    fmt::{self, Write as _},
+    io,
+};
+
 use crate::parser::{
-    HsmlNode, RootNode, attribute::node::AttributeNode, comment::node::CommentNode,
+    HsmlNode, RootNode,
+    attribute::node::AttributeNode,
+    class::node::ClassNode,
+    comment::node::CommentNode,
+    doctype::node::DoctypeNode,
+    span::{Position, Span},
     tag::node::TagNode,
 };
 
-#[derive(Default)]
-pub struct HsmlCompileOptions {}
+use self::{
+    document::compile_document,
+    error::{CompileError, NodeContext},
+    escape::{
+        EscapeQuotes, escape_html_to, escape_jsx_to, escape_xml_to, is_binding_attribute,
+        sanitize_comment, sanitize_jsx_comment,
+    },
+    html::{
+        can_omit_end_tag, is_block_element, is_boolean_attribute, is_component, is_foreign_root,
+        is_html_integration_point, is_void_element, is_xml_name, preserves_whitespace,
+        react_prop_name,
+    },
+    minify::{can_be_unquoted, collapse_line_breaks},
+    source_map::{Mapping, SourceMap},
+    vue::compile_vue_render,
+};
 
-fn compile_tag_node(tag_node: &TagNode, _options: &HsmlCompileOptions) -> String {
-    let mut html_content = String::new();
+pub mod document;
+pub mod error;
+pub mod escape;
+pub mod html;
+pub mod minify;
+pub mod source_map;
+pub mod vue;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EscapePolicy {
+    /// Escape `&`, `<` and `>` in text and attribute values and sanitize `--` in comments.
+    ///
+    /// `{{ ... }}` interpolations, character references and the values of binding attributes
+    /// like `:src`, `@click` or `v-if` are kept as they are.
+    #[default]
+    Html,
+    /// Write text, attribute values and comments as they are.
+    Verbatim,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompileTarget {
+    #[default]
+    Html,
+    /// Well-formed XML, e.g. for XHTML, SVG sprites or RSS feeds.
+    ///
+    /// All elements are closed and boolean attributes get their key as value.
+    Xml,
+    /// JSX for React, e.g. to paste into a `.jsx` or `.tsx` file.
+    ///
+    /// `class` and `for` become `className` and `htmlFor`, all empty elements are closed,
+    /// comments become `{/* ... */}` and multiple root nodes are wrapped in a fragment.
+    /// Doctypes cannot be expressed in JSX and are left out,
+    /// framework bindings like `:class`, `@click` or `v-if` are reported as unsupported.
+    Jsx,
+    /// JavaScript `h()` calls for the render function of a Vue component.
+    ///
+    /// Bindings like `:src` and `@click` become props, directives like `v-if` are reported as unsupported.
+    VueRender,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HsmlMinifyOptions {
+    /// Leave out end tags the HTML parser adds implicitly, e.g. `</li>` before the next `li`.
+    pub omit_optional_end_tags: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct HsmlDebugLocationOptions {
+    /// Name of the attribute that contains the location, `data-hsml-loc` by default.
+    pub attribute_name: String,
+    /// Name of the `.hsml` file as it should appear in the location, e.g. `components/card.hsml`.
+    pub file: String,
+}
+
+impl Default for HsmlDebugLocationOptions {
+    fn default() -> Self {
+        Self {
+            attribute_name: String::from("data-hsml-loc"),
+            file: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HsmlDocumentOptions {
+    /// Language of the `html` element, `en` by default.
+    pub lang: String,
+    /// `utf-8` by default.
+    pub charset: String,
+    /// Content of the viewport meta tag, `width=device-width, initial-scale=1` by default.
+    pub viewport: Option<String>,
+    pub title: Option<String>,
+    /// URLs of the stylesheets linked in the head.
+    pub stylesheets: Vec<String>,
+    /// URLs of the scripts loaded with `defer` in the head.
+    pub scripts: Vec<String>,
+}
+
+impl Default for HsmlDocumentOptions {
+    fn default() -> Self {
+        Self {
+            lang: String::from("en"),
+            charset: String::from("utf-8"),
+            viewport: Some(String::from("width=device-width, initial-scale=1")),
+            title: None,
+            stylesheets: vec![],
+            scripts: vec![],
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct HsmlCompileOptions {
+    pub target: CompileTarget,
+    pub escape: EscapePolicy,
+    /// Write empty component tags like `RouterLink` as `<RouterLink/>`, e.g. for Vue templates.
+    pub self_close_components: bool,
+    /// Indent string for pretty-printed output, `None` writes everything on a single line.
+    ///
+    /// Only content where whitespace is not rendered is put on separate lines,
+    /// so inline text, inline elements and `pre` or `textarea` stay untouched.
+    pub pretty: Option<String>,
+    /// Write the smallest output that is equivalent to the regular one, takes precedence over `pretty`.
+    ///
+    /// Native comments are stripped and line breaks in the expressions of binding attributes like `:class` are collapsed.
+    /// For the HTML target, optional quotes are dropped and boolean attributes like `disabled="disabled"` are collapsed.
+    pub minify: Option<HsmlMinifyOptions>,
+    /// Add an attribute like `data-hsml-loc="card.hsml:12:5"` to every element, so devtools can jump to its source.
+    ///
+    /// Only applies to the HTML target and leaves out void elements and SVG or MathML content.
+    pub debug_locations: Option<HsmlDebugLocationOptions>,
+    /// Wrap the output in a complete document with doctype, `html`, `head` and `body`, e.g. for page fragments.
+    ///
+    /// Dev comments like `// @title About us` at the root level override the options for the file.
+    /// A root level `html` tag and the `head` and `body` tags are merged into the document.
+    /// Only supported by the HTML and XML targets, the others return an error.
+    pub document: Option<HsmlDocumentOptions>,
+}
+
+/// State of the position in the AST the compiler is currently at.
+#[derive(Clone, Copy, Default)]
+struct CompileContext {
+    /// Indentation level of the current node for pretty-printed output.
+    depth: usize,
+    /// Inside of SVG or MathML content.
+    is_foreign: bool,
+    /// Inside of an element like `pre` where whitespace is rendered.
+    preserves_whitespace: bool,
+    /// The end tag of the current node can be left out for minified output.
+    omit_end_tag: bool,
+}
+
+/// Returns whether whitespace around the node is not rendered, so it can be put on its own line.
+fn is_block_node(node: &HsmlNode) -> bool {
+    match node {
+        HsmlNode::Tag(tag_node) => is_block_element(&tag_node.tag),
+        HsmlNode::Text(_) => false,
+        _ => true,
+    }
+}
+
+/// Returns whether the node produces any output.
+fn is_output_node(node: &HsmlNode, options: &HsmlCompileOptions) -> bool {
+    match node {
+        HsmlNode::Comment(comment_node) => !comment_node.is_dev && options.minify.is_none(),
+        HsmlNode::Doctype(_) => options.target != CompileTarget::Jsx,
+        HsmlNode::Error(_) => false,
+        _ => true,
+    }
+}
+
+/// Returns whether the end tag of the node can be left out, `parent` is `None` at the root level.
+fn should_omit_end_tag<N: Borrow<HsmlNode>>(
+    nodes: &[N],
+    index: usize,
+    parent: Option<&str>,
+    options: &HsmlCompileOptions,
+) -> bool {
+    let HsmlNode::Tag(tag_node) = nodes[index].borrow() else {
+        return false;
+    };
+
+    if options.target != CompileTarget::Html
+        || !options
+            .minify
+            .as_ref()
+            .is_some_and(|minify| minify.omit_optional_end_tags)
+    {
+        return false;
+    }
+
+    let next = nodes[index + 1..]
+        .iter()
+        .map(Borrow::borrow)
+        .find(|node| is_output_node(node, options))
+        .map(|node| match node {
+            HsmlNode::Tag(tag_node) => tag_node.tag.as_str(),
+            _ => "",
+        });
+
+    can_omit_end_tag(&tag_node.tag, next, parent)
+}
+
+/// Sink the compiler writes into, keeps track of the position for source maps.
+struct Output<'a, W: fmt::Write> {
+    writer: &'a mut W,
+    /// Only collected when a source map is requested.
+    mappings: Option<Vec<Mapping>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a, W: fmt::Write> Output<'a, W> {
+    fn new(writer: &'a mut W, with_mappings: bool) -> Self {
+        Self {
+            writer,
+            mappings: with_mappings.then(Vec::new),
+            line: 0,
+            column: 0,
+        }
+    }
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.mappings.is_some() {
+            for c in s.chars() {
+                if c == '\n' {
+                    self.line += 1;
+                    self.column = 0;
+                } else {
+                    self.column += c.len_utf16();
+                }
+            }
+        }
+
+        self.writer.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Maps the current position in the output to the position in the source.
+    fn map(&mut self, source: Position) {
+        if let Some(mappings) = &mut self.mappings {
+            mappings.push(Mapping {
+                line: self.line,
+                column: self.column,
+                source,
+            });
+        }
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for Output<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        Output::write_str(self, s)
+    }
+}
+
+/// Compiles the nodes and puts each of them on its own line if they are all block nodes.
+fn compile_nodes<W: fmt::Write, N: Borrow<HsmlNode>>(
+    nodes: &[N],
+    parent: Option<&str>,
+    options: &HsmlCompileOptions,
+    context: CompileContext,
+    can_break: bool,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    let indent_string = options.pretty.as_deref().filter(|_| {
+        options.minify.is_none()
+            && can_break
+            && !context.preserves_whitespace
+            && nodes.iter().all(|node| is_block_node(node.borrow()))
+    });
+
+    let mut has_output = false;
+
+    for (index, node) in nodes.iter().enumerate() {
+        let node = node.borrow();
+
+        if let Some(indent_string) = indent_string {
+            // dev comments and errors do not produce any output
+            if !is_output_node(node, options) {
+                continue;
+            }
+
+            // the first node of the document does not need a line break
+            if context.depth > 0 || has_output {
+                output.write_char('\n')?;
+            }
+
+            for _ in 0..context.depth {
+                output.write_str(indent_string)?;
+            }
+        }
+
+        has_output = true;
+
+        let node_context = CompileContext {
+            omit_end_tag: should_omit_end_tag(nodes, index, parent, options),
+            ..context
+        };
+        compile_node(node, options, node_context, output)?;
+    }
+
+    if let Some(indent_string) = indent_string
+        && has_output
+    {
+        output.write_char('\n')?;
+
+        for _ in 0..context.depth.saturating_sub(1) {
+            output.write_str(indent_string)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl HsmlCompileOptions {
+    /// Writes the text escaped for the target, without allocating.
+    fn write_text<W: fmt::Write>(&self, text: &str, writer: &mut W) -> fmt::Result {
+        match (self.escape, self.target) {
+            (EscapePolicy::Html, CompileTarget::Html) => escape_html_to(text, writer),
+            (EscapePolicy::Html, CompileTarget::Xml) => escape_xml_to(text, writer),
+            (EscapePolicy::Html, CompileTarget::Jsx) => escape_jsx_to(text, writer),
+            // render functions set the text content, which does not need to be escaped
+            (EscapePolicy::Verbatim, _) | (_, CompileTarget::VueRender) => writer.write_str(text),
+        }
+    }
+}
+
+/// Writes expressions as they are and escapes other values for the target.
+fn write_attribute_value<W: fmt::Write>(
+    value: &str,
+    is_expression: bool,
+    options: &HsmlCompileOptions,
+    writer: &mut W,
+) -> fmt::Result {
+    if is_expression {
+        writer.write_str(value)
+    } else {
+        options.write_text(value, writer)
+    }
+}
+
+/// Wraps the value in quotes that are safe for it, so any parsed value results in valid HTML.
+fn compile_attribute_value<W: fmt::Write>(
+    value: &str,
+    is_expression: bool,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    // escaping never adds quotes
+    if !value.contains('"') {
+        output.write_char('"')?;
+        write_attribute_value(value, is_expression, options, output)?;
+        output.write_char('"')
+    } else if !value.contains('\'') {
+        output.write_char('\'')?;
+        write_attribute_value(value, is_expression, options, output)?;
+        output.write_char('\'')
+    } else {
+        output.write_char('"')?;
+        write_attribute_value(value, is_expression, options, &mut EscapeQuotes(output))?;
+        output.write_char('"')
+    }
+}
+
+/// Writes the attribute and returns whether its value was written without quotes.
+fn compile_attribute<W: fmt::Write>(
+    key: &str,
+    value: Option<&str>,
+    source: Position,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<bool, CompileError> {
+    let minify_html = options.minify.is_some() && options.target == CompileTarget::Html;
+
+    output.write_char(' ')?;
+    output.map(source);
+    output.write_str(match options.target {
+        CompileTarget::Jsx => react_prop_name(key),
+        _ => key,
+    })?;
+
+    let value = match value {
+        Some(value) => {
+            // line breaks in literal values like a multiline `title` are rendered
+            let value =
+                if options.minify.is_some() && is_binding_attribute(key) && value.contains('\n') {
+                    Cow::Owned(collapse_line_breaks(value))
+                } else {
+                    Cow::Borrowed(value)
+                };
+
+            // `disabled="disabled"` and `disabled=""` are the same as `disabled`
+            if minify_html
+                && is_boolean_attribute(key)
+                && (value.is_empty() || value.eq_ignore_ascii_case(key))
+            {
+                return Ok(false);
+            }
+
+            value
+        }
+        // XML has no boolean attributes
+        None if options.target == CompileTarget::Xml => Cow::Borrowed(key),
+        None => return Ok(false),
+    };
+
+    // expressions of bindings are evaluated by the framework and must not be escaped
+    let is_expression = is_binding_attribute(key) && options.target == CompileTarget::Html;
+
+    output.write_char('=')?;
+
+    if minify_html && can_be_unquoted(&value) {
+        write_attribute_value(&value, is_expression, options, output)?;
+        return Ok(true);
+    }
+
+    compile_attribute_value(&value, is_expression, options, output)?;
+
+    Ok(false)
+}
+
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).expect("Unable to serialize string")
+}
+
+/// Writes an inline style as the object React expects, e.g. `style={{ fontSize: "12px" }}`.
+fn compile_jsx_style<W: fmt::Write>(
+    value: &str,
+    key_span: Span,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    let space = if options.minify.is_some() { "" } else { " " };
+
+    output.write_char(' ')?;
+    output.map(key_span.start);
+    output.write_str("style={{")?;
+
+    let declarations = value
+        .split(';')
+        .map(str::trim)
+        .filter(|declaration| !declaration.is_empty());
+
+    for (index, declaration) in declarations.enumerate() {
+        let Some((property, property_value)) = declaration.split_once(':') else {
+            return Err(CompileError::Unsupported {
+                feature: format!("style declaration `{}`", declaration),
+                target: "JSX",
+                span: key_span,
+            });
+        };
+        let property = property.trim();
+
+        if index > 0 {
+            output.write_char(',')?;
+        }
+        output.write_str(space)?;
+
+        if property.starts_with("--") {
+            // custom properties keep their name
+            output.write_str(&string_literal(property))?;
+        } else {
+            // `-ms-` is the only vendor prefix that stays lowercase
+            let (prefix, property) = match property.strip_prefix("-ms-") {
+                Some(property) => ("ms-", property),
+                None => ("", property),
+            };
+
+            let mut is_word_start = false;
+            for c in prefix.chars().chain(property.chars()) {
+                if c == '-' {
+                    is_word_start = true;
+                } else if is_word_start {
+                    output.write_char(c.to_ascii_uppercase())?;
+                    is_word_start = false;
+                } else {
+                    output.write_char(c)?;
+                }
+            }
+        }
+
+        output.write_char(':')?;
+        output.write_str(space)?;
+        output.write_str(&string_literal(property_value.trim()))?;
+    }
+
+    output.write_str(space)?;
+    output.write_str("}}")?;
+
+    Ok(())
+}
+
+fn write_class_names<W: fmt::Write>(
+    class_nodes: &[ClassNode],
+    options: &HsmlCompileOptions,
+    writer: &mut W,
+) -> fmt::Result {
+    for (index, class_node) in class_nodes.iter().enumerate() {
+        if index > 0 {
+            writer.write_char(' ')?;
+        }
+
+        options.write_text(&class_node.name, writer)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the class names separated by spaces, like [`compile_attribute`] would write them as one value.
+fn compile_class_attribute<W: fmt::Write>(
+    class_nodes: &[ClassNode],
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<bool, CompileError> {
+    let minify_html = options.minify.is_some() && options.target == CompileTarget::Html;
+
+    output.write_char(' ')?;
+    output.map(class_nodes[0].span.start);
+    output.write_str(match options.target {
+        CompileTarget::Jsx => react_prop_name("class"),
+        _ => "class",
+    })?;
+    output.write_char('=')?;
+
+    if let [class_node] = class_nodes
+        && minify_html
+        && can_be_unquoted(&class_node.name)
+    {
+        options.write_text(&class_node.name, output)?;
+        return Ok(true);
+    }
+
+    // arbitrary values like `content-['a']` can contain quotes
+    let contains = |c: char| {
+        class_nodes
+            .iter()
+            .any(|class_node| class_node.name.contains(c))
+    };
+
+    if !contains('"') {
+        output.write_char('"')?;
+        write_class_names(class_nodes, options, output)?;
+        output.write_char('"')?;
+    } else if !contains('\'') {
+        output.write_char('\'')?;
+        write_class_names(class_nodes, options, output)?;
+        output.write_char('\'')?;
+    } else {
+        output.write_char('"')?;
+        write_class_names(class_nodes, options, &mut EscapeQuotes(&mut *output))?;
+        output.write_char('"')?;
+    }
+
+    Ok(false)
+}
+
+/// Writes the id, classes, attributes and debug location of the tag and returns whether the last value was written without quotes.
+fn compile_attributes<W: fmt::Write>(
+    tag_node: &TagNode,
+    is_foreign: bool,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<bool, CompileError> {
+    let mut is_last_value_unquoted = false;
+
+    if let Some(id_node) = &tag_node.id {
+        is_last_value_unquoted =
+            compile_attribute("id", Some(&id_node.id), id_node.span.start, options, output)?;
+    }
+
+    if let Some(class_nodes) = &tag_node.classes {
+        is_last_value_unquoted = compile_class_attribute(class_nodes, options, output)?;
+    }
+
+    if let Some(attributes) = &tag_node.attributes {
+        for node in attributes {
+            match node {
+                HsmlNode::Attribute(AttributeNode {
+                    key,
+                    value,
+                    key_span,
+                    ..
+                }) => {
+                    // JSX has its own syntax for expressions and event handlers
+                    if options.target == CompileTarget::Jsx && is_binding_attribute(key) {
+                        return Err(CompileError::Unsupported {
+                            feature: format!("`{}` binding", key),
+                            target: "JSX",
+                            span: *key_span,
+                        });
+                    }
+
+                    // React only accepts an object for the inline style
+                    if options.target == CompileTarget::Jsx
+                        && key == "style"
+                        && let Some(value) = value
+                    {
+                        compile_jsx_style(value, *key_span, options, output)?;
+                        is_last_value_unquoted = false;
+                        continue;
+                    }
+
+                    // XML parsers reject names like `@click` or `[value]`
+                    if options.target == CompileTarget::Xml && !is_xml_name(key) {
+                        return Err(CompileError::Unsupported {
+                            feature: format!("`{}` attribute", key),
+                            target: "XML",
+                            span: *key_span,
+                        });
+                    }
+
+                    is_last_value_unquoted =
+                        compile_attribute(key, value.as_deref(), key_span.start, options, output)?;
+                }
+                HsmlNode::Comment(node) if node.is_dev => {
+                    // do nothing
+                }
+                node => return Err(CompileError::unexpected_node(node, NodeContext::Attributes)),
+            }
+        }
+    }
+
+    if let Some(debug_locations) = &options.debug_locations
+        && options.target == CompileTarget::Html
+        && !is_foreign
+        && !is_void_element(&tag_node.tag)
+    {
+        let start = tag_node.span.start;
+        output.write_char(' ')?;
+        output.map(start);
+        output.write_str(&debug_locations.attribute_name)?;
+        output.write_char('=')?;
+
+        // written in parts, so that no string is built for each tag
+        is_last_value_unquoted = options.minify.is_some() && can_be_unquoted(&debug_locations.file);
+        if is_last_value_unquoted {
+            options.write_text(&debug_locations.file, output)?;
+            write!(output, ":{}:{}", start.line, start.column)?;
+        } else {
+            output.write_char('"')?;
+            options.write_text(&debug_locations.file, &mut EscapeQuotes(&mut *output))?;
+            write!(output, ":{}:{}\"", start.line, start.column)?;
+        }
+    }
+
+    Ok(is_last_value_unquoted)
+}
+
+fn compile_tag_node<W: fmt::Write>(
+    tag_node: &TagNode,
+    options: &HsmlCompileOptions,
+    context: CompileContext,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    let is_foreign = context.is_foreign || is_foreign_root(&tag_node.tag);
+    let is_empty = tag_node.children.is_none() && tag_node.text.is_none();
+
+    // the content would be dropped, or moved out of the element by the HTML parser
+    if matches!(options.target, CompileTarget::Html | CompileTarget::Jsx)
+        && !is_foreign
+        && !is_empty
+        && is_void_element(&tag_node.tag)
+    {
+        return Err(CompileError::VoidElementContent {
+            tag: tag_node.tag.clone(),
+            span: tag_node.span,
+        });
+    }
+
+    output.map(tag_node.span.start);
+    output.write_char('<')?;
+    output.write_str(&tag_node.tag)?;
+
+    // an unquoted value needs to be separated from a following `/>`
+    let is_last_value_unquoted = compile_attributes(tag_node, is_foreign, options, output)?;
+
+    let should_auto_close = match options.target {
+        CompileTarget::Html => {
+            is_void_element(&tag_node.tag)
+                || (is_empty && is_foreign)
+                || (is_empty && options.self_close_components && is_component(&tag_node.tag))
+        }
+        CompileTarget::Xml | CompileTarget::Jsx | CompileTarget::VueRender => is_empty,
+    };
+    if should_auto_close {
+        if options.minify.is_some() && options.target == CompileTarget::Html {
+            if is_void_element(&tag_node.tag) {
+                // the slash of void elements is ignored by the HTML parser
+                return Ok(output.write_char('>')?);
+            }
+
+            if is_last_value_unquoted {
+                output.write_char(' ')?;
+            }
+        }
+
+        return Ok(output.write_str("/>")?);
+    } else {
+        output.write_char('>')?;
+    }
+
+    if let Some(text) = &tag_node.text {
+        output.map(text.span.start);
+        options.write_text(&text.text, output)?;
+    }
+
+    if let Some(child_nodes) = &tag_node.children {
+        let child_context = CompileContext {
+            depth: context.depth + 1,
+            is_foreign: is_foreign && !is_html_integration_point(&tag_node.tag),
+            preserves_whitespace: context.preserves_whitespace
+                || preserves_whitespace(&tag_node.tag),
+            omit_end_tag: false,
+        };
+
+        // the children are inline content if they follow text or are inside of an inline element
+        let can_break = tag_node.text.is_none() && is_block_element(&tag_node.tag);
+
+        compile_nodes(
+            child_nodes,
+            Some(&tag_node.tag),
+            options,
+            child_context,
+            can_break,
+            output,
+        )?;
+    }
+
+    if context.omit_end_tag {
+        return Ok(());
+    }
+
+    // the end tag belongs to the tag as well
+    output.map(tag_node.span.start);
+    output.write_str("</")?;
+    output.write_str(&tag_node.tag)?;
+    output.write_char('>')?;
+
+    Ok(())
+}
+
+fn compile_comment_node<W: fmt::Write>(
+    comment_node: &CommentNode,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    output.map(comment_node.span.start);
+
+    if options.target == CompileTarget::Jsx {
+        output.write_str("{/*")?;
+
+        match options.escape {
+            EscapePolicy::Html => output.write_str(&sanitize_jsx_comment(&comment_node.text))?,
+            EscapePolicy::Verbatim => output.write_str(&comment_node.text)?,
+        }
+
+        return output.write_str(" */}");
+    }
+
+    output.write_str("<!--")?;
+
+    match options.escape {
+        EscapePolicy::Html => output.write_str(&sanitize_comment(&comment_node.text))?,
+        EscapePolicy::Verbatim => output.write_str(&comment_node.text)?,
+    }
+
+    output.write_str(" -->")
+}
+
+fn compile_doctype_node<W: fmt::Write>(
+    doctype_node: &DoctypeNode,
+    _options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> fmt::Result {
+    output.map(doctype_node.span.start);
+
+    // same shorthands as pug, everything else is used as it is
+    let declaration = match doctype_node.value.as_str() {
+        "" | "html" => "<!DOCTYPE html>",
+        "xml" => r#"<?xml version="1.0" encoding="utf-8" ?>"#,
+        "transitional" => {
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">"#
+        }
+        "strict" => {
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">"#
+        }
+        "frameset" => {
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Frameset//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-frameset.dtd">"#
+        }
+        "1.1" => {
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.1//EN" "http://www.w3.org/TR/xhtml11/DTD/xhtml11.dtd">"#
+        }
+        "basic" => {
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML Basic 1.1//EN" "http://www.w3.org/TR/xhtml-basic/xhtml-basic11.dtd">"#
+        }
+        "mobile" => {
+            r#"<!DOCTYPE html PUBLIC "-//WAPFORUM//DTD XHTML Mobile 1.2//EN" "http://www.openmobilealliance.org/tech/DTD/xhtml-mobile12.dtd">"#
+        }
+        "plist" => {
+            r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#
+        }
+        value => {
+            output.write_str("<!DOCTYPE ")?;
+            output.write_str(value)?;
+            return output.write_char('>');
+        }
+    };
+
+    output.write_str(declaration)
+}
+
+fn compile_node<W: fmt::Write>(
+    node: &HsmlNode,
+    options: &HsmlCompileOptions,
+    context: CompileContext,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    match node {
+        HsmlNode::Doctype(doctype_node) if is_output_node(node, options) => {
+            compile_doctype_node(doctype_node, options, output)?
+        }
+        HsmlNode::Doctype(_) => {}
+        HsmlNode::Tag(tag_node) => compile_tag_node(tag_node, options, context, output)?,
+        HsmlNode::Comment(comment_node) if is_output_node(node, options) => {
+            compile_comment_node(comment_node, options, output)?
+        }
+        HsmlNode::Comment(_) => {}
+        HsmlNode::Text(text_node) => {
+            output.map(text_node.span.start);
+            options.write_text(&text_node.text, output)?
+        }
+        // source that could not be parsed is left out
+        HsmlNode::Error(_) => {}
+        node => {
+            let node_context = if context.depth == 0 {
+                NodeContext::Root
+            } else {
+                NodeContext::Children
+            };
+
+            return Err(CompileError::unexpected_node(node, node_context));
+        }
+    }
+
+    Ok(())
+}
+
+fn compile_root<W: fmt::Write>(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+    output: &mut Output<W>,
+) -> Result<(), CompileError> {
+    if let Some(document) = &options.document {
+        let target = match options.target {
+            CompileTarget::Html | CompileTarget::Xml => {
+                return compile_document(hsml_ast, document, options, output);
+            }
+            CompileTarget::Jsx => "JSX",
+            CompileTarget::VueRender => "Vue render function",
+        };
+
+        return Err(CompileError::UnsupportedOption {
+            option: "document",
+            target,
+        });
+    }
+
+    if options.target == CompileTarget::VueRender {
+        return compile_vue_render(hsml_ast, options, output);
+    }
+
+    let mut root_nodes = hsml_ast
+        .nodes
+        .iter()
+        .filter(|node| is_output_node(node, options));
+
+    // a JSX expression must consist of a single element
+    let needs_fragment = options.target == CompileTarget::Jsx
+        && !matches!(
+            (root_nodes.next(), root_nodes.next()),
+            (Some(HsmlNode::Tag(_)), None)
+        );
+
+    if !needs_fragment {
+        return compile_nodes(
+            &hsml_ast.nodes,
+            None,
+            options,
+            CompileContext::default(),
+            true,
+            output,
+        );
+    }
+
+    output.write_str("<>")?;
+
+    let fragment_context = CompileContext {
+        depth: 1,
+        ..Default::default()
+    };
+    compile_nodes(
+        &hsml_ast.nodes,
+        None,
+        options,
+        fragment_context,
+        true,
+        output,
+    )?;
+
+    output.write_str("</>")?;
+
+    Ok(())
+}
+
+/// Compiles the AST straight into the writer, without building the whole output in memory first.
+pub fn compile_to<W: fmt::Write>(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+    writer: &mut W,
+) -> Result<(), CompileError> {
+    compile_root(hsml_ast, options, &mut Output::new(writer, false))
+}
+
+/// Adapts an `io::Write` to `fmt::Write` and keeps the actual error, which `fmt::Error` cannot hold.
+struct IoWriter<W: io::Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Compiles the AST straight into a file, socket or any other `io::Write`.
+///
+/// The writer is not buffered, so wrap it into a `BufWriter` if it does not buffer by itself.
+pub fn compile_to_writer<W: io::Write>(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+    writer: W,
+) -> Result<(), CompileError> {
+    let mut writer = IoWriter {
+        writer,
+        error: None,
+    };
+
+    compile_to(hsml_ast, options, &mut writer).map_err(|err| match (err, writer.error.take()) {
+        (CompileError::Fmt(_), Some(err)) => CompileError::Io(err),
+        (err, _) => err,
+    })
+}
+
+pub fn compile(hsml_ast: &RootNode, options: &HsmlCompileOptions) -> Result<String, CompileError> {
+    let mut html_content = String::new();
+
+    compile_to(hsml_ast, options, &mut html_content)?;
+
+    Ok(html_content)
+}
+
+/// Compiles the AST and maps every emitted tag, attribute and text back to its position in the source.
+///
+/// `file` is the name of the compiled file and `source` the name of the `.hsml` file, as they should appear in the map.
+pub fn compile_with_source_map(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+    file: &str,
+    source: &str,
+) -> Result<(String, SourceMap), CompileError> {
+    let mut html_content = String::new();
+    let mut output = Output::new(&mut html_content, true);
+
+    compile_root(hsml_ast, options, &mut output)?;
+
+    let mappings = output.mappings.unwrap_or_default();
+
+    Ok((html_content, SourceMap::new(file, source, &mappings)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compiler::{
+            CompileTarget, EscapePolicy, HsmlCompileOptions, HsmlDebugLocationOptions,
+            HsmlDocumentOptions, HsmlMinifyOptions, compile, compile_to, compile_to_writer,
+            compile_with_source_map,
+            error::{CompileError, NodeContext},
+        },
+        parser::{
+            HsmlNode, RootNode, class::node::ClassNode, id::node::IdNode, parse::parse,
+            span::LineIndex, tag::node::TagNode, text::node::TextNode,
+        },
+    };
+
+    #[test]
+    fn it_should_compile_empty_ast() {
+        let ast = RootNode { nodes: vec![] };
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(html_content, "");
+    }
+
+    #[test]
+    fn it_should_compile_simple_tag() {
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Tag(TagNode {
+                tag: String::from("h1"),
+                id: None,
+                classes: None,
+                attributes: None,
+                text: Some(TextNode {
+                    text: String::from("Hello World"),
+                    ..Default::default()
+                }),
+                children: None,
+                ..Default::default()
+            })],
+        };
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(html_content, "<h1>Hello World</h1>");
+    }
+
+    #[test]
+    fn it_should_compile_content_with_id() {
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Tag(TagNode {
+                tag: String::from("h1"),
+                id: Some(IdNode {
+                    id: String::from("title"),
+                    ..Default::default()
+                }),
+                classes: None,
+                attributes: None,
+                text: Some(TextNode {
+                    text: String::from("Hello World"),
+                    ..Default::default()
+                }),
+                children: None,
+                ..Default::default()
+            })],
+        };
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(html_content, r#"<h1 id="title">Hello World</h1>"#);
+    }
+
+    #[test]
+    fn it_should_compile_parsed_content() {
+        let input = r#"h1.text-red Vite CJS Faker Demo
+.card
+  .card__image
+    img(:src="natureImageUrl" :alt="'Background image for ' + fullName")
+  .card__profile
+    img(:src="avatarUrl" :alt="'Avatar image of ' + fullName")
+  .card__body {{ fullName }}
+"#;
+
+        let (rest, ast) = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<h1 class="text-red">Vite CJS Faker Demo</h1><div class="card"><div class="card__image"><img :src="natureImageUrl" :alt="'Background image for ' + fullName"/></div><div class="card__profile"><img :src="avatarUrl" :alt="'Avatar image of ' + fullName"/></div><div class="card__body">{{ fullName }}</div></div>"#
+        );
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_compile_doctype() {
+        let input = "doctype html\nhtml\n  body text\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            "<!DOCTYPE html><html><body>text</body></html>"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_doctype_shorthands() {
+        let compile_doctype = |input: &str| {
+            let (_, ast) = parse(input).unwrap();
+            compile(&ast, &HsmlCompileOptions::default()).unwrap()
+        };
+
+        assert_eq!(compile_doctype("doctype\n"), "<!DOCTYPE html>");
+        assert_eq!(
+            compile_doctype("doctype xml\n"),
+            r#"<?xml version="1.0" encoding="utf-8" ?>"#
+        );
+        assert_eq!(
+            compile_doctype("doctype transitional\n"),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">"#
+        );
+        assert_eq!(
+            compile_doctype("doctype html SYSTEM \"about:legacy-compat\"\n"),
+            r#"<!DOCTYPE html SYSTEM "about:legacy-compat">"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_piped_text() {
+        let input = r##"| Read the
+p
+  | Click
+  a(href="#") here
+  |  to continue
+"##;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r##"Read the<p>Click<a href="#">here</a> to continue</p>"##
+        );
+    }
+
+    #[test]
+    fn it_should_compile_block_expansion() {
+        let input = "ul.md:flex: li.item: a(href=\"/\") Home\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<ul class="md:flex"><li class="item"><a href="/">Home</a></li></ul>"#
+        );
+    }
+
+    #[test]
+    fn it_should_not_nest_indented_lines_below_inline_text() {
+        let (_, ast) = parse("p text\n  b bold\n").unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            "<p>text</p><b>bold</b>"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_unquoted_attribute_values() {
+        let input = "img(width=384, height=216 alt=\"\")\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<img width="384" height="216" alt=""/>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_attribute_values_with_safe_quotes() {
+        let input = r#"a(title='He said "hi"' :class="{ 'active': isActive }" data-both="it's \"x\"")
+abbr(title="say \"hi\"") hi
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<a title='He said "hi"' :class="{ 'active': isActive }" data-both="it's &quot;x&quot;"></a><abbr title='say "hi"'>hi</abbr>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_class_names_with_quotes() {
+        let input = r#"p.content-["a"].b hi
+p.c.d
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<p class='content-["a"] b'>hi</p><p class="c d"></p>"#
+        );
+    }
+
+    #[test]
+    fn it_should_escape_text_attribute_values_and_comments() {
+        let input = r#"//! a -- b
+p(title="a < b & c" :disabled="a < b && c" @click="x > 0") 1 < 2 &amp; {{ a && b }}
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<!-- a - - b --><p title="a &lt; b &amp; c" :disabled="a < b && c" @click="x > 0">1 &lt; 2 &amp; {{ a && b }}</p>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_verbatim() {
+        let input = "//! a -- b\np(title=\"a < b\") 1 < 2 &\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    escape: EscapePolicy::Verbatim,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            r#"<!-- a -- b --><p title="a < b">1 < 2 &</p>"#
+        );
+    }
+
+    #[test]
+    fn it_should_only_self_close_void_elements() {
+        let input = "div(v-if=\"isDM\")\nslot\nbr\nimg(src=\"/a.png\")\nRouterLink\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<div v-if="isDM"></div><slot></slot><br/><img src="/a.png"/><RouterLink></RouterLink>"#
+        );
+    }
+
+    #[test]
+    fn it_should_self_close_components() {
+        let input = "RouterLink\nslot\nRouterView text\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    self_close_components: true,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            "<RouterLink/><slot></slot><RouterView>text</RouterView>"
+        );
+    }
+
+    #[test]
+    fn it_should_self_close_empty_foreign_elements() {
+        let input = r#"svg(viewBox="0 0 10 10")
+  path(d="M0 0")
+  foreignObject
+    div
+math
+  mspace
+span
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<svg viewBox="0 0 10 10"><path d="M0 0"/><foreignObject><div></div></foreignObject></svg><math><mspace/></math><span></span>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_xml() {
+        let input = r##"doctype xml
+svg:svg(xmlns:svg="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink")
+  svg:use(xlink:href="#icon")
+  svg:text &nbsp;&amp; {{ a < b }}
+input(disabled)
+div
+br
+"##;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    target: CompileTarget::Xml,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            r##"<?xml version="1.0" encoding="utf-8" ?><svg:svg xmlns:svg="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><svg:use xlink:href="#icon"/><svg:text>&amp;nbsp;&amp; {{ a &lt; b }}</svg:text></svg:svg><input disabled="disabled"/><div/><br/>"##
+        );
+    }
+
+    #[test]
+    fn it_should_compile_pretty() {
+        let input = r#"doctype html
+//! card
+.card
+  // dev comment
+  .card__image
+    img(src="/a.png")
+  p Hello World
+  p
+    | Click
+    a(href="/") here
+  ul
+    li: a(href="/") Home
+    li
+  pre
+    div
+      span  keep
+  div
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    pretty: Some(String::from("  ")),
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            r#"<!DOCTYPE html>
+<!-- card -->
+<div class="card">
+  <div class="card__image"><img src="/a.png"/></div>
+  <p>Hello World</p>
+  <p>Click<a href="/">here</a></p>
+  <ul>
+    <li><a href="/">Home</a></li>
+    <li></li>
+  </ul>
+  <pre><div><span> keep</span></div></pre>
+  <div></div>
+</div>
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_not_pretty_print_custom_elements_and_components() {
+        let input = "div\n  my-icon\n  my-badge\nsection\n  RouterLink\n    p Home\n  p Text\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    pretty: Some(String::from("  ")),
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            "<div><my-icon></my-icon><my-badge></my-badge></div>\n<section><RouterLink><p>Home</p></RouterLink><p>Text</p></section>\n"
+        );
+    }
+
+    #[test]
+    fn it_should_not_pretty_print_inline_root_nodes() {
+        let input = "span a\n| text\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    pretty: Some(String::from("\t")),
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            "<span>a</span>text"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_minified() {
+        let input = r#"//! note
+form#login.card(action="/login" method='post')
+  input(type="checkbox" checked="checked" disabled value="")
+  input(type="text" placeholder="Your name" :class="{
+    'is-a': isA,
+    'is-b': isB,
+  }")
+  RouterLink(to=home)
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                self_close_components: true,
+                pretty: Some(String::from("  ")),
+                minify: Some(HsmlMinifyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<form id=login class=card action=/login method=post><input type=checkbox checked disabled value=""><input type=text placeholder="Your name" :class="{ 'is-a': isA, 'is-b': isB, }"><RouterLink to=home /></form>"#
+        );
+    }
+
+    #[test]
+    fn it_should_keep_values_of_non_boolean_attributes_in_minified_output() {
+        let (_, ast) = parse("input(name=\"name\" value=\"value\" hidden=\"hidden\")\n").unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                minify: Some(HsmlMinifyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(html_content, "<input name=name value=value hidden>");
+    }
+
+    #[test]
+    fn it_should_keep_line_breaks_of_literal_values_in_minified_output() {
+        let (_, ast) = parse("abbr(title=\"a\n  b\" :class=\"[\n  a,\n  b\n]\") x\n").unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                minify: Some(HsmlMinifyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            "<abbr title=\"a\n  b\" :class=\"[ a, b ]\">x</abbr>"
+        );
+    }
+
+    #[test]
+    fn it_should_omit_optional_end_tags() {
+        let input = r#"ul
+  li One
+  li Two
+  // dev comment
+div
+  p First
+  p Second
+  span Third
+a(href="/")
+  p Link
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                minify: Some(HsmlMinifyOptions {
+                    omit_optional_end_tags: true,
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<ul><li>One<li>Two</ul><div><p>First<p>Second</p><span>Third</span></div><a href=/><p>Link</p></a>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_minified_xml() {
+        let input = r#"//! note
+svg(viewBox="0 0 10 10" hidden)
+  rect(width="10")
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                target: CompileTarget::Xml,
+                minify: Some(HsmlMinifyOptions {
+                    omit_optional_end_tags: true,
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<svg viewBox="0 0 10 10" hidden="hidden"><rect width="10"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_with_source_map() {
+        let input = "div#app\n  p(title=\"a\") Hello\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        let (html_content, source_map) = compile_with_source_map(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(String::from("  ")),
+                ..Default::default()
+            },
+            "index.html",
+            "index.hsml",
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            "<div id=\"app\">\n  <p title=\"a\">Hello</p>\n</div>\n"
+        );
+        assert_eq!(source_map.sources, vec!["index.hsml"]);
+        // `<div`, ` id`, `<p`, ` title`, `Hello`, `</p>` and `</div>`
+        assert_eq!(source_map.mappings, "AAAA,KAAG;EACD,GAAE,UAAW,KAAb;AADF");
+    }
+
+    #[test]
+    fn it_should_compile_debug_locations() {
+        let input = r#"div.card
+  img(src="a.png")
+  svg
+    path
+  p: span Text
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                debug_locations: Some(HsmlDebugLocationOptions {
+                    file: String::from("card.hsml"),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<div class="card" data-hsml-loc="card.hsml:1:1"><img src="a.png"/><svg><path/></svg><p data-hsml-loc="card.hsml:5:3"><span data-hsml-loc="card.hsml:5:6">Text</span></p></div>"#
+        );
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                debug_locations: Some(HsmlDebugLocationOptions {
+                    attribute_name: String::from("data-loc"),
+                    file: String::from("card.hsml"),
+                }),
+                minify: Some(HsmlMinifyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(html_content.starts_with("<div class=card data-loc=card.hsml:1:1>"));
+    }
+
+    #[test]
+    fn it_should_compile_debug_locations_only_for_html() {
+        let input = "div.card Text
+";
+
+        let (_, ast) = parse(input).unwrap();
+
+        for (target, expected) in [
+            (CompileTarget::Xml, "<div class=\"card\">Text</div>"),
+            (CompileTarget::Jsx, "<div className=\"card\">Text</div>"),
+            (
+                CompileTarget::VueRender,
+                "h(\"div\", { class: \"card\" }, \"Text\")",
+            ),
+        ] {
+            let content = compile(
+                &ast,
+                &HsmlCompileOptions {
+                    target,
+                    debug_locations: Some(HsmlDebugLocationOptions {
+                        file: String::from("card.hsml"),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            assert_eq!(content.trim_end(), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_compile_into_writer() {
+        let input = "ul\n  li One\n  li Two\n";
+
+        let (_, ast) = parse(input).unwrap();
+        let options = HsmlCompileOptions {
+            pretty: Some(String::from("  ")),
+            ..Default::default()
+        };
+
+        let mut html_content = String::new();
+        compile_to(&ast, &options, &mut html_content).unwrap();
+
+        let mut bytes: Vec<u8> = vec![];
+        compile_to_writer(&ast, &options, &mut bytes).unwrap();
+
+        assert_eq!(
+            html_content,
+            "<ul>\n  <li>One</li>\n  <li>Two</li>\n</ul>\n"
+        );
+        assert_eq!(String::from_utf8(bytes).unwrap(), html_content);
+        assert_eq!(compile(&ast, &options).unwrap(), html_content);
+    }
+
+    #[test]
+    fn it_should_compile_jsx() {
+        let input = r#"//! Sign in
+form.card(onSubmit="submit")
+  label(for="name") Name {required}
+  input#name(type="text" disabled)
+  // dev comment
+  p
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<>{/* Sign in */}<form className="card" onSubmit="submit"><label htmlFor="name">Name &#123;required&#125;</label><input id="name" type="text" disabled/><p/></form></>"#
+        );
 
-    html_content.push('<');
-    html_content.push_str(&tag_node.tag);
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            pretty: Some(String::from("  ")),
+            ..Default::default()
+        };
 
-    if let Some(id_node) = &tag_node.id {
-        html_content.push_str(r#" id=""#);
-        html_content.push_str(&id_node.id);
-        html_content.push('\"');
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<>
+  {/* Sign in */}
+  <form className="card" onSubmit="submit"><label htmlFor="name">Name &#123;required&#125;</label><input id="name" type="text" disabled/><p/></form>
+</>"#
+        );
     }
 
-    if let Some(class_nodes) = &tag_node.classes {
-        html_content.push_str(r#" class=""#);
+    #[test]
+    fn it_should_compile_jsx_style_and_prop_names() {
+        let input = r#"div(style="color: red; font-size: 12px; -webkit-line-clamp: 2; --gap: 1rem;")
+  input(readonly autofocus tabindex="-1" maxlength="4" aria-label="Code")
+"#;
 
-        let class_names: String = class_nodes
-            .iter()
-            .map(|class_node| class_node.name.as_str())
-            .collect::<Vec<&str>>()
-            .join(" ");
+        let (_, ast) = parse(input).unwrap();
 
-        html_content.push_str(&class_names);
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            ..Default::default()
+        };
 
-        html_content.push('\"');
-    }
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<div style={{ color: "red", fontSize: "12px", WebkitLineClamp: "2", "--gap": "1rem" }}><input readOnly autoFocus tabIndex="-1" maxLength="4" aria-label="Code"/></div>"#
+        );
 
-    if let Some(attributes) = &tag_node.attributes {
-        attributes.iter().for_each(|node| match node {
-            HsmlNode::Attribute(AttributeNode { key, value }) => {
-                html_content.push(' ');
-                html_content.push_str(key);
-
-                if let Some(value) = value {
-                    html_content.push_str(r#"=""#);
-                    html_content.push_str(value);
-                    html_content.push('\"');
-                }
-            }
-            HsmlNode::Comment(node) if node.is_dev => {
-                // do nothing
-            }
-            _ => panic!("Unsupported node type"),
-        });
-    }
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            minify: Some(Default::default()),
+            ..Default::default()
+        };
 
-    let should_auto_close = tag_node.children.is_none() && tag_node.text.is_none();
-    if should_auto_close {
-        html_content.push_str("/>");
-        return html_content;
-    } else {
-        html_content.push('>');
-    }
+        let (_, ast) = parse("p(style=\"-ms-transform: none\") a\n").unwrap();
 
-    if let Some(text) = &tag_node.text {
-        html_content.push_str(&text.text);
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<p style={{msTransform:"none"}}>a</p>"#
+        );
     }
 
-    if let Some(child_nodes) = &tag_node.children {
-        for child_node in child_nodes {
-            match child_node {
-                HsmlNode::Tag(tag_node) => {
-                    html_content.push_str(&compile_tag_node(tag_node, _options))
-                }
-                HsmlNode::Comment(comment_node) => {
-                    if !comment_node.is_dev {
-                        html_content.push_str(&compile_comment_node(comment_node, _options))
-                    }
-                }
-                _ => panic!("Unsupported node type"),
-            }
-        }
-    }
+    #[test]
+    fn it_should_not_wrap_single_jsx_root_element_in_fragment() {
+        let input = "doctype html\n// dev comment\ndiv\n  | a */ b\n  //! c */ d\n";
 
-    html_content.push_str("</");
-    html_content.push_str(&tag_node.tag);
-    html_content.push('>');
+        let (_, ast) = parse(input).unwrap();
 
-    html_content
-}
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                target: CompileTarget::Jsx,
+                escape: EscapePolicy::Html,
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-fn compile_comment_node(comment_node: &CommentNode, _options: &HsmlCompileOptions) -> String {
-    let mut html_content = String::new();
+        assert_eq!(html_content, "<div>a */ b{/* c * / d */}</div>");
 
-    html_content.push_str("<!--");
-    html_content.push_str(&comment_node.text);
-    html_content.push_str(" -->");
+        let (_, ast) = parse("| Hello\n").unwrap();
 
-    html_content
-}
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                target: CompileTarget::Jsx,
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-fn compile_node(node: &HsmlNode, options: &HsmlCompileOptions) -> String {
-    match node {
-        HsmlNode::Tag(tag_node) => compile_tag_node(tag_node, options),
-        HsmlNode::Comment(comment_node) if !comment_node.is_dev => {
-            compile_comment_node(comment_node, options)
-        }
-        HsmlNode::Comment(_) => String::from(""),
-        _ => panic!("Unsupported node type"),
+        assert_eq!(html_content, "<>Hello</>");
     }
-}
 
-pub fn compile(hsml_ast: &RootNode, options: &HsmlCompileOptions) -> String {
-    let mut html_content = String::new();
+    #[test]
+    fn it_should_compile_vue_render_function() {
+        let input = r#"//! note
+.card#main(:class="{ active: isActive }" @click="toggle" @update:model-value="value = $event")
+  h1.title Hello {{ user.name }}!
+  input(type="checkbox" disabled :checked="done")
+  RouterLink(to="/home" :aria-label="$t('nav.home')") Home
+  router-link(to="/about") About
+  | Footer
+"#;
 
-    for node in &hsml_ast.nodes {
-        html_content.push_str(&compile_node(node, options));
-    }
+        let (_, ast) = parse(input).unwrap();
 
-    html_content
-}
+        let options = HsmlCompileOptions {
+            target: CompileTarget::VueRender,
+            ..Default::default()
+        };
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        compiler::{HsmlCompileOptions, compile},
-        parser::{
-            HsmlNode, RootNode, id::node::IdNode, parse::parse, tag::node::TagNode,
-            text::node::TextNode,
-        },
-    };
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"h("div", { id: "main", class: ["card", { active: isActive }], onClick: toggle, "onUpdate:modelValue": $event => (value = $event) }, [h("h1", { class: "title" }, "Hello " + toDisplayString(user.name) + "!"), h("input", { type: "checkbox", disabled: "", checked: done }), h(RouterLink, { to: "/home", "aria-label": $t('nav.home') }, { default: () => "Home" }), h(resolveComponent("router-link"), { to: "/about" }, { default: () => "About" }), "Footer"])"#
+        );
+
+        let options = HsmlCompileOptions {
+            target: CompileTarget::VueRender,
+            pretty: Some(String::from("  ")),
+            ..Default::default()
+        };
+
+        let (_, ast) = parse("ul\n  li(:key=\"a\") A\n  li B\np\n").unwrap();
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"[
+  h("ul", null, [
+    h("li", {
+      key: a
+    }, "A"),
+    h("li", null, "B")
+  ]),
+  h("p")
+]"#
+        );
+    }
 
     #[test]
-    fn it_should_compile_empty_ast() {
-        let ast = RootNode { nodes: vec![] };
+    fn it_should_compile_parsed_elk_main_content_component_to_vue_render_function() {
+        let input = r#"div(ref="container" :class="containerClass")
+  .sticky.top-0.z10(
+    pt="[env(safe-area-inset-top,0)]"
+  )
+    NuxtLink.items-center.btn-text(
+      flex="~ gap1"
+      :aria-label="$t('nav.back')"
+      @click="$router.go(-1)"
+    )
+      .rtl-flip(i-ri:arrow-left-line)
+  PwaInstallPrompt.lg:hidden
+"#;
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let (_, ast) = parse(input).unwrap();
 
-        assert_eq!(html_content, "");
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                target: CompileTarget::VueRender,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"h("div", { class: containerClass, ref: "container" }, [h("div", { class: "sticky top-0 z10", pt: "[env(safe-area-inset-top,0)]" }, [h(NuxtLink, { class: "items-center btn-text", flex: "~ gap1", "aria-label": $t('nav.back'), onClick: $event => ($router.go(-1)) }, { default: () => [h("div", { class: "rtl-flip", "i-ri:arrow-left-line": "" })] })]), h(PwaInstallPrompt, { class: "lg:hidden" })])"#
+        );
     }
 
     #[test]
-    fn it_should_compile_simple_tag() {
-        let ast = RootNode {
-            nodes: vec![HsmlNode::Tag(TagNode {
-                tag: String::from("h1"),
-                id: None,
-                classes: None,
-                attributes: None,
-                text: Some(TextNode {
-                    text: String::from("Hello World"),
-                }),
-                children: None,
-            })],
+    fn it_should_compile_document() {
+        let (_, ast) = parse("// @title About & contact\nmain\n  h1 About\n").unwrap();
+
+        let options = HsmlCompileOptions {
+            document: Some(HsmlDocumentOptions {
+                stylesheets: vec![String::from("/main.css")],
+                scripts: vec![String::from("/main.js")],
+                ..Default::default()
+            }),
+            ..Default::default()
         };
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8"/><meta name="viewport" content="width=device-width, initial-scale=1"/><title>About &amp; contact</title><link rel="stylesheet" href="/main.css"/><script src="/main.js" defer></script></head><body><main><h1>About</h1></main></body></html>"#
+        );
+
+        let options = HsmlCompileOptions {
+            pretty: Some(String::from("  ")),
+            ..options
+        };
 
-        assert_eq!(html_content, "<h1>Hello World</h1>");
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8"/>
+    <meta name="viewport" content="width=device-width, initial-scale=1"/>
+    <title>About &amp; contact</title>
+    <link rel="stylesheet" href="/main.css"/>
+    <script src="/main.js" defer></script>
+  </head>
+  <body>
+    <main>
+      <h1>About</h1>
+    </main>
+  </body>
+</html>
+"#
+        );
     }
 
     #[test]
-    fn it_should_compile_content_with_id() {
-        let ast = RootNode {
-            nodes: vec![HsmlNode::Tag(TagNode {
-                tag: String::from("h1"),
-                id: Some(IdNode {
-                    id: String::from("title"),
-                }),
-                classes: None,
-                attributes: None,
-                text: Some(TextNode {
-                    text: String::from("Hello World"),
-                }),
-                children: None,
-            })],
-        };
+    fn it_should_merge_document_with_head_and_body() {
+        let input = r#"// @lang de
+// @stylesheet /page.css
+head
+  title Start
+  link(rel="stylesheet" href="/main.css")
+body.home
+  p Hallo
+footer Impressum
+"#;
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let (_, ast) = parse(input).unwrap();
 
-        assert_eq!(html_content, r#"<h1 id="title">Hello World</h1>"#);
+        let options = HsmlCompileOptions {
+            document: Some(HsmlDocumentOptions {
+                title: Some(String::from("Fallback")),
+                viewport: None,
+                stylesheets: vec![String::from("/main.css")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<!DOCTYPE html><html lang="de"><head><meta charset="utf-8"/><link rel="stylesheet" href="/page.css"/><title>Start</title><link rel="stylesheet" href="/main.css"/></head><body class="home"><p>Hallo</p><footer>Impressum</footer></body></html>"#
+        );
     }
 
     #[test]
-    fn it_should_compile_parsed_content() {
-        let input = r#"h1.text-red Vite CJS Faker Demo
-.card
-  .card__image
-    img(:src="natureImageUrl" :alt="'Background image for ' + fullName")
-  .card__profile
-    img(:src="avatarUrl" :alt="'Avatar image of ' + fullName")
-  .card__body {{ fullName }}
+    fn it_should_merge_document_with_html() {
+        let input = r#"doctype html
+html(lang="fr" data-theme="dark")
+  // @title Accueil
+  head
+    meta(charset="utf-8")
+  body
+    p Bonjour
 "#;
 
-        let (rest, ast) = parse(input).unwrap();
+        let (_, ast) = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let options = HsmlCompileOptions {
+            document: Some(HsmlDocumentOptions {
+                viewport: None,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
 
         assert_eq!(
-            html_content,
-            r#"<h1 class="text-red">Vite CJS Faker Demo</h1><div class="card"><div class="card__image"><img :src="natureImageUrl" :alt="'Background image for ' + fullName"/></div><div class="card__profile"><img :src="avatarUrl" :alt="'Avatar image of ' + fullName"/></div><div class="card__body">{{ fullName }}</div></div>"#
+            compile(&ast, &options).unwrap(),
+            r#"<!DOCTYPE html><html lang="fr" data-theme="dark"><head><title>Accueil</title><meta charset="utf-8"/></head><body><p>Bonjour</p></body></html>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_xml_document() {
+        let (_, ast) = parse("body Hello\n").unwrap();
+
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Xml,
+            document: Some(HsmlDocumentOptions {
+                scripts: vec![String::from("/main.js")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8"/><meta name="viewport" content="width=device-width, initial-scale=1"/><script src="/main.js" defer="defer"></script></head><body>Hello</body></html>"#
         );
-        assert_eq!(rest, "");
     }
 
     #[test]
@@ -227,7 +1979,7 @@ figure.md:flex.bg-slate-100.rounded-xl.p-8.md:p-0.dark:bg-slate-800/10
 
         let (rest, ast) = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(
             html_content,
@@ -276,14 +2028,21 @@ and the build size is tiny."</p></blockquote><figcaption class="font-medium"><di
 
         let (rest, ast) = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                self_close_components: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
         assert_eq!(
             html_content,
             r#"<div class="space-y-3" :class="{
     'pt2 pb0.5 px3.5 bg-dm rounded-4 me--1': isDM,
     'ms--3.5 mt--1 ms--1': isDM && context !== 'details',
-  }"><StatusBody v-if="(!isFiltered && isSensitiveNonSpoiler) || hideAllMedia" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusSpoiler :enabled="hasSpoilerOrSensitiveMedia || isFiltered" :filter="isFiltered" :sensitive-non-spoiler="isSensitiveNonSpoiler || hideAllMedia" :is-d-m="isDM"><template v-if="spoilerTextPresent" #spoiler><p>{{ status.spoilerText }}</p></template><template v-else-if="filterPhrase" #spoiler><p>{{ `${$t('status.filter_hidden_phrase')}: ${filterPhrase}` }}</p></template><StatusBody v-if="!(isSensitiveNonSpoiler || hideAllMedia)" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusTranslation :status="status"/><StatusPoll v-if="status.poll" :status="status"/><StatusMedia v-if="status.mediaAttachments?.length" :status="status" :is-preview="isPreview"/><StatusPreviewCard v-if="status.card" :card="status.card" :small-picture-only="status.mediaAttachments?.length > 0"/><StatusCard v-if="status.reblog" :status="status.reblog" border="~ rounded" :actions="false"/><div v-if="isDM"/></StatusSpoiler></div>"#
+  }"><StatusBody v-if="(!isFiltered && isSensitiveNonSpoiler) || hideAllMedia" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusSpoiler :enabled="hasSpoilerOrSensitiveMedia || isFiltered" :filter="isFiltered" :sensitive-non-spoiler="isSensitiveNonSpoiler || hideAllMedia" :is-d-m="isDM"><template v-if="spoilerTextPresent" #spoiler><p>{{ status.spoilerText }}</p></template><template v-else-if="filterPhrase" #spoiler><p>{{ `${$t('status.filter_hidden_phrase')}: ${filterPhrase}` }}</p></template><StatusBody v-if="!(isSensitiveNonSpoiler || hideAllMedia)" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusTranslation :status="status"/><StatusPoll v-if="status.poll" :status="status"/><StatusMedia v-if="status.mediaAttachments?.length" :status="status" :is-preview="isPreview"/><StatusPreviewCard v-if="status.card" :card="status.card" :small-picture-only="status.mediaAttachments?.length > 0"/><StatusCard v-if="status.reblog" :status="status.reblog" border="~ rounded" :actions="false"/><div v-if="isDM"></div></StatusSpoiler></div>"#
         );
         assert_eq!(rest, "");
     }
@@ -325,12 +2084,329 @@ and the build size is tiny."</p></blockquote><figcaption class="font-medium"><di
 
         let (rest, ast) = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                self_close_components: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
         assert_eq!(
             html_content,
-            r#"<div ref="container" :class="containerClass"><div class="sticky top-0 z10 backdrop-blur native:lg:w-[calc(100vw-5rem)] native:xl:w-[calc(135%+(100vw-1200px)/2)]" pt="[env(safe-area-inset-top,0)]" bg="[rgba(var(--rgb-bg-base),0.7)]"><div class="flex justify-between px5 py2 native:xl:flex" :class="{ 'xl:hidden': $route.name !== 'tag' }" border="b base"><div class="flex gap-3 items-center py2 w-full" :overflow-hidden="!noOverflowHidden ? '' : false"><NuxtLink class="items-center btn-text p-0 xl:hidden" v-if="backOnSmallScreen || back" flex="~ gap1" :aria-label="$t('nav.back')" @click="$router.go(-1)"><div class="rtl-flip" i-ri:arrow-left-line/></NuxtLink><div class="flex w-full native-mac:justify-center native-mac:text-center native-mac:sm:justify-start" :truncate="!noOverflowHidden ? '' : false" data-tauri-drag-region><slot name="title"/></div><div class="sm:hidde nh-7 w-1px"/></div><div class="flex items-center flex-shrink-0 gap-x-2"><slot name="actions"/><PwaBadge class="lg:hidden"/><NavUser v-if="isHydrated"/><NavUserSkeleton v-else/></div></div><slot name="header"><div hidden/></slot></div><PwaInstallPrompt class="lg:hidden"/><div class="m-auto" :class="isHydrated && wideLayout ? 'xl:w-full sm:max-w-600px' : 'sm:max-w-600px md:shrink-0'"><div class="h-6" hidden :class="{ 'xl:block': $route.name !== 'tag' && !$slots.header }"/><slot/></div></div>"#
+            r#"<div ref="container" :class="containerClass"><div class="sticky top-0 z10 backdrop-blur native:lg:w-[calc(100vw-5rem)] native:xl:w-[calc(135%+(100vw-1200px)/2)]" pt="[env(safe-area-inset-top,0)]" bg="[rgba(var(--rgb-bg-base),0.7)]"><div class="flex justify-between px5 py2 native:xl:flex" :class="{ 'xl:hidden': $route.name !== 'tag' }" border="b base"><div class="flex gap-3 items-center py2 w-full" :overflow-hidden="!noOverflowHidden ? '' : false"><NuxtLink class="items-center btn-text p-0 xl:hidden" v-if="backOnSmallScreen || back" flex="~ gap1" :aria-label="$t('nav.back')" @click="$router.go(-1)"><div class="rtl-flip" i-ri:arrow-left-line></div></NuxtLink><div class="flex w-full native-mac:justify-center native-mac:text-center native-mac:sm:justify-start" :truncate="!noOverflowHidden ? '' : false" data-tauri-drag-region><slot name="title"></slot></div><div class="sm:hidde nh-7 w-1px"></div></div><div class="flex items-center flex-shrink-0 gap-x-2"><slot name="actions"></slot><PwaBadge class="lg:hidden"/><NavUser v-if="isHydrated"/><NavUserSkeleton v-else/></div></div><slot name="header"><div hidden></div></slot></div><PwaInstallPrompt class="lg:hidden"/><div class="m-auto" :class="isHydrated && wideLayout ? 'xl:w-full sm:max-w-600px' : 'sm:max-w-600px md:shrink-0'"><div class="h-6" hidden :class="{ 'xl:block': $route.name !== 'tag' && !$slots.header }"></div><slot></slot></div></div>"#
         );
         assert_eq!(rest, "");
     }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_return_error_of_writer() {
+        struct FailingWriter;
+
+        impl std::io::Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "closed",
+                ))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (_, ast) = parse("div\n").unwrap();
+
+        let err =
+            compile_to_writer(&ast, &HsmlCompileOptions::default(), FailingWriter).unwrap_err();
+
+        let CompileError::Io(err) = err else {
+            panic!("expected an io error");
+        };
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn it_should_return_error_for_unexpected_nodes() {
+        let (_, mut ast) = parse("div\n  p\n").unwrap();
+        let line_index = LineIndex::new("div\n  .a\n");
+
+        let HsmlNode::Tag(div_node) = &mut ast.nodes[0] else {
+            panic!("expected a tag node");
+        };
+        div_node.children = Some(vec![HsmlNode::Class(ClassNode {
+            name: String::from("a"),
+            span: line_index.span(6, 8),
+        })]);
+
+        let err = compile(&ast, &HsmlCompileOptions::default()).unwrap_err();
+
+        assert!(matches!(
+            err,
+            CompileError::UnexpectedNode {
+                node: "class",
+                context: NodeContext::Children,
+                span: Some(_),
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "error: Unexpected class node in the children of a tag at 2:3"
+        );
+
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Root(RootNode { nodes: vec![] })],
+        };
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default())
+                .unwrap_err()
+                .to_string(),
+            "error: Unexpected root node at the root level"
+        );
+
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Tag(TagNode {
+                tag: String::from("div"),
+                attributes: Some(vec![HsmlNode::Tag(TagNode::default())]),
+                ..Default::default()
+            })],
+        };
+
+        assert!(matches!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap_err(),
+            CompileError::UnexpectedNode {
+                node: "tag",
+                context: NodeContext::Attributes,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn it_should_return_error_for_void_elements_with_content() {
+        let (_, ast) = parse("p\n  br Text\n").unwrap();
+
+        let err = compile(&ast, &HsmlCompileOptions::default()).unwrap_err();
+
+        assert!(matches!(&err, CompileError::VoidElementContent { tag, .. } if tag == "br"));
+        assert_eq!(
+            err.to_string(),
+            "error: Void element `br` cannot have content at 2:3"
+        );
+
+        let (_, ast) = parse("img(src=\"a.png\")\n  span Caption\n").unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default())
+                .unwrap_err()
+                .to_string(),
+            "error: Void element `img` cannot have content at 1:1"
+        );
+
+        // XML has no void elements
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Xml,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r#"<img src="a.png"><span>Caption</span></img>"#
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_invalid_xml_attribute_names() {
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Xml,
+            ..Default::default()
+        };
+
+        for (input, expected) in [
+            (
+                "rect(@click=\"go\")\n",
+                "error: Unsupported `@click` attribute in the XML target at 1:6",
+            ),
+            (
+                "rect(x=\"1\" :class=\"c\")\n",
+                "error: Unsupported `:class` attribute in the XML target at 1:12",
+            ),
+            (
+                "input([value]=\"v\")\n",
+                "error: Unsupported `[value]` attribute in the XML target at 1:7",
+            ),
+            (
+                "template(#slot)\n",
+                "error: Unsupported `#slot` attribute in the XML target at 1:10",
+            ),
+        ] {
+            let (_, ast) = parse(input).unwrap();
+
+            assert_eq!(compile(&ast, &options).unwrap_err().to_string(), expected);
+        }
+
+        let (_, ast) = parse("use(xlink:href=\"#a\" v-if=\"b\")\n").unwrap();
+
+        assert_eq!(
+            compile(&ast, &options).unwrap(),
+            r##"<use xlink:href="#a" v-if="b"/>"##
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_bindings_to_jsx() {
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            ..Default::default()
+        };
+
+        for (input, expected) in [
+            (
+                "div(@click=\"go\" :class=\"c\")\n",
+                "error: Unsupported `@click` binding in the JSX target at 1:5",
+            ),
+            (
+                "div\n  p(:class=\"c\")\n",
+                "error: Unsupported `:class` binding in the JSX target at 2:5",
+            ),
+            (
+                "p(v-if=\"a\")\n",
+                "error: Unsupported `v-if` binding in the JSX target at 1:3",
+            ),
+            (
+                "template(#header)\n",
+                "error: Unsupported `#header` binding in the JSX target at 1:10",
+            ),
+        ] {
+            let (_, ast) = parse(input).unwrap();
+
+            let err = compile(&ast, &options).unwrap_err();
+
+            assert!(matches!(
+                err,
+                CompileError::Unsupported { target: "JSX", .. }
+            ));
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_for_duplicate_document_elements() {
+        let options = HsmlCompileOptions {
+            document: Some(HsmlDocumentOptions::default()),
+            ..Default::default()
+        };
+
+        for (input, expected) in [
+            (
+                "head\n  title A\nhead\n  title B\n",
+                "error: Duplicate `head` element in the document at 3:1",
+            ),
+            (
+                "html\n  body\n    p A\nbody\n  p B\n",
+                "error: Duplicate `body` element in the document at 4:1",
+            ),
+            (
+                "html\n  p A\nhtml\n  p B\n",
+                "error: Duplicate `html` element in the document at 3:1",
+            ),
+        ] {
+            let (_, ast) = parse(input).unwrap();
+
+            assert_eq!(compile(&ast, &options).unwrap_err().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_for_invalid_jsx_style() {
+        let (_, ast) = parse("div\n  p(style=\"color\")\n").unwrap();
+
+        let options = HsmlCompileOptions {
+            target: CompileTarget::Jsx,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            compile(&ast, &options).unwrap_err().to_string(),
+            "error: Unsupported style declaration `color` in the JSX target at 2:5"
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_document_to_jsx_or_vue_render_function() {
+        let (_, ast) = parse("p Hello\n").unwrap();
+
+        for (target, expected) in [
+            (
+                CompileTarget::Jsx,
+                "error: The document option is not supported by the JSX target",
+            ),
+            (
+                CompileTarget::VueRender,
+                "error: The document option is not supported by the Vue render function target",
+            ),
+        ] {
+            let options = HsmlCompileOptions {
+                target,
+                document: Some(HsmlDocumentOptions::default()),
+                ..Default::default()
+            };
+
+            let err = compile(&ast, &options).unwrap_err();
+
+            assert!(matches!(
+                err,
+                CompileError::UnsupportedOption {
+                    option: "document",
+                    ..
+                }
+            ));
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn it_should_not_compile_vue_directives_to_render_function() {
+        let input = r#"template(v-if="spoilerTextPresent" #spoiler)
+  p {{ status.spoilerText }}
+StatusBody(
+  v-else
+  :status="status"
+)
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let options = HsmlCompileOptions {
+            target: CompileTarget::VueRender,
+            ..Default::default()
+        };
+
+        let err = compile(&ast, &options).unwrap_err();
+
+        assert!(matches!(err, CompileError::Unsupported { .. }));
+        assert_eq!(
+            err.to_string(),
+            "error: Unsupported `v-if` directive in the Vue render function target at 1:10"
+        );
+
+        let (_, ast) = parse(&input[input.find("StatusBody").unwrap()..]).unwrap();
+
+        assert_eq!(
+            compile(&ast, &options).unwrap_err().to_string(),
+            "error: Unsupported `v-else` directive in the Vue render function target at 2:3"
+        );
+
+        let (_, ast) = parse("button(@click.stop=\"open\")\n").unwrap();
+
+        assert_eq!(
+            compile(&ast, &options).unwrap_err().to_string(),
+            "error: Unsupported event modifier `.stop` in the Vue render function target at 1:8"
+        );
+    }
 }
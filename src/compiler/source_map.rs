@@ -0,0 +1,190 @@
+use serde::Serialize;
+
+use crate::parser::span::Position;
+
+/// A position in the compiled output and the position in the source it originates from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    /// 0-based line in the output.
+    pub line: usize,
+    /// 0-based column in the output, counted in UTF-16 code units.
+    pub column: usize,
+    pub source: Position,
+}
+
+/// A Source Map v3, see https://tc39.es/ecma426/
+///
+/// Generated columns are counted in UTF-16 code units, source columns in bytes like the spans of the AST.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceMap {
+    pub version: u8,
+    /// Name of the compiled file, e.g. `index.html`.
+    pub file: String,
+    /// Names of the source files, e.g. `index.hsml`.
+    pub sources: Vec<String>,
+    pub names: Vec<String>,
+    /// Base64 VLQ encoded segments, one group per line of the output separated by `;`.
+    pub mappings: String,
+}
+
+impl SourceMap {
+    /// Builds the source map from mappings in the order they were written to the output.
+    pub fn new(file: &str, source: &str, mappings: &[Mapping]) -> Self {
+        Self {
+            version: 3,
+            file: file.to_string(),
+            sources: vec![source.to_string()],
+            names: vec![],
+            mappings: encode_mappings(mappings),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Unable to serialize source map")
+    }
+}
+
+fn encode_mappings(mappings: &[Mapping]) -> String {
+    let mut encoded = String::new();
+
+    let mut line = 0;
+    let mut previous: Option<&Mapping> = None;
+
+    // every field except the generated column is relative to the previous segment of the whole map
+    let mut previous_column = 0;
+    let mut previous_source_line = 0;
+    let mut previous_source_column = 0;
+
+    for mapping in mappings {
+        // only the outermost node is kept if several of them start at the same position
+        if previous.is_some_and(|previous| {
+            (previous.line, previous.column) == (mapping.line, mapping.column)
+        }) {
+            continue;
+        }
+
+        if previous.is_some() && line == mapping.line {
+            encoded.push(',');
+        }
+
+        while line < mapping.line {
+            encoded.push(';');
+            line += 1;
+            previous_column = 0;
+        }
+
+        let column = mapping.column as i64;
+        let source_line = mapping.source.line.saturating_sub(1) as i64;
+        let source_column = mapping.source.column.saturating_sub(1) as i64;
+
+        encode_vlq(&mut encoded, column - previous_column);
+        // there is only a single source
+        encode_vlq(&mut encoded, 0);
+        encode_vlq(&mut encoded, source_line - previous_source_line);
+        encode_vlq(&mut encoded, source_column - previous_source_column);
+
+        previous = Some(mapping);
+        previous_column = column;
+        previous_source_line = source_line;
+        previous_source_column = source_column;
+    }
+
+    encoded
+}
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Writes the value as base64 VLQ, the least significant bit of the first digit is the sign.
+fn encode_vlq(encoded: &mut String, value: i64) {
+    let mut vlq = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+
+        if vlq > 0 {
+            // continuation bit
+            digit |= 0b100000;
+        }
+
+        encoded.push(BASE64_CHARS[digit] as char);
+
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compiler::source_map::{Mapping, SourceMap, encode_vlq},
+        parser::span::Position,
+    };
+
+    fn position(line: usize, column: usize) -> Position {
+        Position {
+            offset: 0,
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn it_should_encode_vlq() {
+        for (value, expected) in [
+            (0, "A"),
+            (1, "C"),
+            (-1, "D"),
+            (15, "e"),
+            (16, "gB"),
+            (-17, "jB"),
+        ] {
+            let mut encoded = String::new();
+            encode_vlq(&mut encoded, value);
+            assert_eq!(encoded, expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn it_should_encode_mappings() {
+        // <div>
+        //   <p>ä</p>
+        // </div>
+        let mappings = [
+            Mapping {
+                line: 0,
+                column: 0,
+                source: position(1, 1),
+            },
+            Mapping {
+                line: 1,
+                column: 2,
+                source: position(2, 3),
+            },
+            Mapping {
+                line: 1,
+                column: 5,
+                source: position(2, 5),
+            },
+            // only the first of several mappings at the same position is kept
+            Mapping {
+                line: 1,
+                column: 5,
+                source: position(3, 1),
+            },
+        ];
+
+        let source_map = SourceMap::new("index.html", "index.hsml", &mappings);
+
+        assert_eq!(source_map.mappings, "AAAA;EACE,GAAE");
+        assert_eq!(
+            source_map.to_json(),
+            r#"{"version":3,"file":"index.html","sources":["index.hsml"],"names":[],"mappings":"AAAA;EACE,GAAE"}"#
+        );
+    }
+}
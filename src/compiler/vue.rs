@@ -0,0 +1,435 @@
+use std::fmt;
+
+use crate::{
+    compiler::{
+        HsmlCompileOptions,
+        error::{CompileError, NodeContext},
+        html::{is_component, is_custom_element},
+        minify::collapse_line_breaks,
+        string_literal,
+    },
+    parser::{HsmlNode, RootNode, attribute::node::AttributeNode, span::Span, tag::node::TagNode},
+};
+
+const TARGET_NAME: &str = "Vue render function";
+
+/// Content of an element in the order it appears in the output.
+enum Child<'a> {
+    Text(&'a str),
+    Tag(&'a TagNode),
+}
+
+fn unsupported(feature: String, span: Span) -> CompileError {
+    CompileError::Unsupported {
+        feature,
+        target: TARGET_NAME,
+        span,
+    }
+}
+
+/// Returns the text and tag nodes, comments, doctypes and errors do not produce any output.
+fn collect_children<'a>(
+    nodes: &'a [HsmlNode],
+    context: NodeContext,
+) -> Result<Vec<Child<'a>>, CompileError> {
+    let mut children = vec![];
+
+    for node in nodes {
+        match node {
+            HsmlNode::Tag(tag_node) => children.push(Child::Tag(tag_node)),
+            HsmlNode::Text(text_node) => children.push(Child::Text(&text_node.text)),
+            HsmlNode::Comment(_) | HsmlNode::Doctype(_) | HsmlNode::Error(_) => {}
+            node => return Err(CompileError::unexpected_node(node, context)),
+        }
+    }
+
+    Ok(children)
+}
+
+/// Returns whether the key can be written without quotes in an object literal.
+fn is_identifier(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Turns `Hello {{ name }}` into `"Hello " + toDisplayString(name)`.
+fn text_expression(text: &str) -> String {
+    let mut parts = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{")
+        && let Some(end) = rest[start..].find("}}")
+    {
+        if start > 0 {
+            parts.push(string_literal(&rest[..start]));
+        }
+
+        let expression = rest[start + 2..start + end].trim();
+        parts.push(format!("toDisplayString({})", expression));
+
+        rest = &rest[start + end + 2..];
+    }
+
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(string_literal(rest));
+    }
+
+    parts.join(" + ")
+}
+
+/// Turns an event name like `update:model-value` into the prop `onUpdate:modelValue`.
+fn handler_key(event: &str) -> String {
+    let mut key = String::from("on");
+    let mut is_word_start = true;
+
+    for c in event.chars() {
+        if c == '-' {
+            is_word_start = true;
+            continue;
+        }
+
+        if is_word_start {
+            key.extend(c.to_uppercase());
+        } else {
+            key.push(c);
+        }
+
+        is_word_start = false;
+    }
+
+    key
+}
+
+/// Wraps inline statements like `count++` into an arrow function, handler references stay as they are.
+fn handler_expression(value: &str) -> String {
+    let is_member_expression = !value.starts_with(|c: char| c.is_ascii_digit())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.'));
+
+    if is_member_expression || value.contains("=>") || value.starts_with("function") {
+        value.to_string()
+    } else {
+        format!("$event => ({})", value)
+    }
+}
+
+/// Collects the props of the tag as pairs of key and JavaScript expression.
+fn collect_props(
+    tag_node: &TagNode,
+    options: &HsmlCompileOptions,
+) -> Result<Vec<(String, String)>, CompileError> {
+    let mut id = None;
+    let mut classes = vec![];
+    let mut styles = vec![];
+    let mut props = vec![];
+
+    if let Some(id_node) = &tag_node.id {
+        id = Some(string_literal(&id_node.id));
+    }
+
+    if let Some(class_nodes) = &tag_node.classes {
+        let class_names: Vec<&str> = class_nodes
+            .iter()
+            .map(|class_node| class_node.name.as_str())
+            .collect();
+
+        classes.push(string_literal(&class_names.join(" ")));
+    }
+
+    for node in tag_node.attributes.iter().flatten() {
+        let AttributeNode {
+            key,
+            value,
+            key_span,
+            ..
+        } = match node {
+            HsmlNode::Attribute(attribute_node) => attribute_node,
+            HsmlNode::Comment(comment_node) if comment_node.is_dev => continue,
+            node => return Err(CompileError::unexpected_node(node, NodeContext::Attributes)),
+        };
+
+        // expressions are written as they are, so line breaks only need to be collapsed for minified output
+        let expression = |value: &str| match options.minify {
+            Some(_) => collapse_line_breaks(value),
+            None => value.to_string(),
+        };
+
+        if let Some(name) = key
+            .strip_prefix(':')
+            .or_else(|| key.strip_prefix("v-bind:"))
+        {
+            // `:id` is the same as `:id="id"`
+            let value = expression(value.as_deref().unwrap_or(name));
+
+            match name {
+                "class" => classes.push(value),
+                "style" => styles.push(value),
+                _ => props.push((name.to_string(), value)),
+            }
+        } else if let Some(event) = key.strip_prefix('@').or_else(|| key.strip_prefix("v-on:")) {
+            if event.starts_with('[') {
+                return Err(unsupported(String::from("dynamic event name"), *key_span));
+            }
+
+            if let Some((_, modifiers)) = event.split_once('.') {
+                return Err(unsupported(
+                    format!("event modifier `.{}`", modifiers),
+                    *key_span,
+                ));
+            }
+
+            let handler = match value {
+                Some(value) => handler_expression(&expression(value)),
+                None => String::from("() => {}"),
+            };
+
+            props.push((handler_key(event), handler));
+        } else if key.starts_with("v-") || key.starts_with('#') {
+            return Err(unsupported(format!("`{}` directive", key), *key_span));
+        } else {
+            let value = string_literal(value.as_deref().unwrap_or(""));
+
+            match key.as_str() {
+                "class" => classes.push(value),
+                "style" => styles.push(value),
+                _ => props.push((key.clone(), value)),
+            }
+        }
+    }
+
+    // Vue normalizes arrays of static and bound values
+    let merge = |values: Vec<String>| match values.len() {
+        1 => values.into_iter().next(),
+        _ => (!values.is_empty()).then(|| format!("[{}]", values.join(", "))),
+    };
+
+    let mut all_props = vec![];
+    all_props.extend(id.map(|id| (String::from("id"), id)));
+    all_props.extend(merge(classes).map(|class| (String::from("class"), class)));
+    all_props.extend(merge(styles).map(|style| (String::from("style"), style)));
+    all_props.extend(props);
+
+    Ok(all_props)
+}
+
+/// Writes a line break and the indentation for pretty-printed output.
+fn write_line_break<W: fmt::Write>(
+    options: &HsmlCompileOptions,
+    depth: usize,
+    writer: &mut W,
+) -> fmt::Result {
+    if let Some(indent_string) = &options.pretty {
+        writer.write_char('\n')?;
+
+        for _ in 0..depth {
+            writer.write_str(indent_string)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the items separated by commas between the brackets, each on its own line for pretty-printed output.
+fn write_list<W: fmt::Write, T>(
+    brackets: (&str, &str),
+    items: &[T],
+    options: &HsmlCompileOptions,
+    depth: usize,
+    writer: &mut W,
+    mut write_item: impl FnMut(&T, usize, &mut W) -> Result<(), CompileError>,
+) -> Result<(), CompileError> {
+    writer.write_str(brackets.0)?;
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            writer.write_char(',')?;
+
+            if options.pretty.is_none() {
+                writer.write_char(' ')?;
+            }
+        }
+
+        write_line_break(options, depth + 1, writer)?;
+        write_item(item, depth + 1, writer)?;
+    }
+
+    if !items.is_empty() {
+        write_line_break(options, depth, writer)?;
+    }
+
+    writer.write_str(brackets.1)?;
+
+    Ok(())
+}
+
+/// Writes a single child as it is and multiple children as array.
+fn write_children<W: fmt::Write>(
+    children: &[Child],
+    options: &HsmlCompileOptions,
+    depth: usize,
+    writer: &mut W,
+) -> Result<(), CompileError> {
+    match children {
+        [Child::Text(text)] => writer.write_str(&text_expression(text))?,
+        children => write_list(
+            ("[", "]"),
+            children,
+            options,
+            depth,
+            writer,
+            |child, depth, writer| match child {
+                Child::Text(text) => Ok(writer.write_str(&text_expression(text))?),
+                Child::Tag(tag_node) => write_tag_node(tag_node, options, depth, writer),
+            },
+        )?,
+    }
+
+    Ok(())
+}
+
+fn write_tag_node<W: fmt::Write>(
+    tag_node: &TagNode,
+    options: &HsmlCompileOptions,
+    depth: usize,
+    writer: &mut W,
+) -> Result<(), CompileError> {
+    // directives on the element are the more specific problem
+    let props = collect_props(tag_node, options)?;
+
+    if matches!(tag_node.tag.as_str(), "slot" | "template") {
+        return Err(unsupported(
+            format!("`{}` element", tag_node.tag),
+            tag_node.tag_span,
+        ));
+    }
+
+    let mut children = vec![];
+    if let Some(text_node) = &tag_node.text {
+        children.push(Child::Text(&text_node.text));
+    }
+    if let Some(child_nodes) = &tag_node.children {
+        children.extend(collect_children(child_nodes, NodeContext::Children)?);
+    }
+
+    writer.write_str("h(")?;
+
+    // components have to be in scope, e.g. imported in `<script setup>`
+    if is_component(&tag_node.tag) {
+        writer.write_str(&tag_node.tag)?;
+    } else if is_custom_element(&tag_node.tag) {
+        // kebab-case components are looked up by name, Vue falls back to a custom element
+        writer.write_str("resolveComponent(")?;
+        writer.write_str(&string_literal(&tag_node.tag))?;
+        writer.write_char(')')?;
+    } else {
+        writer.write_str(&string_literal(&tag_node.tag))?;
+    }
+
+    if !props.is_empty() {
+        writer.write_str(", ")?;
+
+        let brackets = match options.pretty {
+            Some(_) => ("{", "}"),
+            None => ("{ ", " }"),
+        };
+
+        write_list(
+            brackets,
+            &props,
+            options,
+            depth,
+            writer,
+            |(key, value), _, writer| {
+                if is_identifier(key) {
+                    writer.write_str(key)?;
+                } else {
+                    writer.write_str(&string_literal(key))?;
+                }
+
+                writer.write_str(": ")?;
+                writer.write_str(value)?;
+
+                Ok(())
+            },
+        )?;
+    } else if !children.is_empty() {
+        writer.write_str(", null")?;
+    }
+
+    if !children.is_empty() {
+        writer.write_str(", ")?;
+
+        if is_component(&tag_node.tag) || is_custom_element(&tag_node.tag) {
+            // children of components are passed as default slot, elements accept it as well
+            writer.write_str("{ default: () => ")?;
+            write_children(&children, options, depth, writer)?;
+            writer.write_str(" }")?;
+        } else {
+            write_children(&children, options, depth, writer)?;
+        }
+    }
+
+    writer.write_char(')')?;
+
+    Ok(())
+}
+
+/// Compiles the AST into an expression of Vue `h()` calls, e.g. for the `render` option of a component.
+///
+/// `h`, `toDisplayString` and the used components have to be in scope of the expression,
+/// just like the variables used in bindings and interpolations.
+/// Tags with a `-` like `router-link` are resolved with `resolveComponent`, so it has to be in scope as well.
+pub fn compile_vue_render<W: fmt::Write>(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+    writer: &mut W,
+) -> Result<(), CompileError> {
+    let children = collect_children(&hsml_ast.nodes, NodeContext::Root)?;
+
+    match children.as_slice() {
+        [] => writer.write_str("null")?,
+        [Child::Tag(tag_node)] => write_tag_node(tag_node, options, 0, writer)?,
+        children => write_children(children, options, 0, writer)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::vue::{handler_expression, handler_key, text_expression};
+
+    #[test]
+    fn it_should_compile_text_expression() {
+        assert_eq!(text_expression("Hello"), r#""Hello""#);
+        assert_eq!(text_expression(""), r#""""#);
+        assert_eq!(
+            text_expression(r#"Hi "{{ user.name }}"!"#),
+            r#""Hi \"" + toDisplayString(user.name) + "\"!""#
+        );
+        assert_eq!(
+            text_expression("{{ a }}{{ b"),
+            "toDisplayString(a) + \"{{ b\""
+        );
+    }
+
+    #[test]
+    fn it_should_compile_handler_key() {
+        assert_eq!(handler_key("click"), "onClick");
+        assert_eq!(handler_key("update:model-value"), "onUpdate:modelValue");
+    }
+
+    #[test]
+    fn it_should_compile_handler_expression() {
+        assert_eq!(handler_expression("submit"), "submit");
+        assert_eq!(handler_expression("form.submit"), "form.submit");
+        assert_eq!(handler_expression("() => open()"), "() => open()");
+        assert_eq!(
+            handler_expression("$router.go(-1)"),
+            "$event => ($router.go(-1))"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::{
+    compiler::error::NodeContext,
+    parser::{HsmlNode, span::Span},
+};
+
+#[derive(Debug)]
+pub enum FormatError {
+    /// A node that cannot appear at its position, e.g. a class node among the children of a tag.
+    ///
+    /// The parser never produces such an AST, but one that is built by hand or deserialized could.
+    UnexpectedNode {
+        /// The `type` of the node as in the AST JSON, e.g. `class`.
+        node: &'static str,
+        context: NodeContext,
+        /// `None` for nodes without a location like the root node.
+        span: Option<Span>,
+    },
+}
+
+impl FormatError {
+    pub fn unexpected_node(node: &HsmlNode, context: NodeContext) -> Self {
+        FormatError::UnexpectedNode {
+            node: node.type_name(),
+            context,
+            span: node.span(),
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FormatError::UnexpectedNode { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnexpectedNode {
+                node,
+                context,
+                span,
+            } => {
+                write!(f, "error: Unexpected {} node {}", node, context)?;
+
+                if let Some(span) = span {
+                    write!(f, " at {}:{}", span.start.line, span.start.column)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
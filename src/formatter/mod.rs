@@ -0,0 +1,533 @@
+use crate::{
+    compiler::error::NodeContext,
+    parser::{
+        HsmlNode, RootNode,
+        attribute::node::{AttributeNode, AttributeQuote},
+        comment::node::CommentNode,
+        tag::node::TagNode,
+    },
+};
+
+use self::error::FormatError;
+
+pub mod error;
+
+pub struct HsmlFormatOptions {
+    /// The string used for one level of indentation.
+    pub indent_string: String,
+    /// Attributes get wrapped onto one line each if the tag would exceed this width.
+    pub print_width: usize,
+}
+
+impl Default for HsmlFormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_string: String::from("  "),
+            print_width: 80,
+        }
+    }
+}
+
+fn format_attribute_node(attribute_node: &AttributeNode) -> String {
+    let mut hsml_content = String::new();
+
+    hsml_content.push_str(&attribute_node.key);
+
+    if let Some(value) = &attribute_node.value {
+        // keep single quotes, as the value might contain double quotes
+        let quote = match attribute_node.quote {
+            AttributeQuote::Single => '\'',
+            AttributeQuote::Double | AttributeQuote::Unquoted => '"',
+        };
+
+        hsml_content.push('=');
+        hsml_content.push(quote);
+        // the parser removed the backslashes of the escaped quotes and backslashes
+        hsml_content.push_str(
+            &value
+                .replace('\\', "\\\\")
+                .replace(quote, &format!("\\{}", quote)),
+        );
+        hsml_content.push(quote);
+    }
+
+    hsml_content
+}
+
+fn format_comment_node(comment_node: &CommentNode) -> String {
+    let mut hsml_content = String::new();
+
+    hsml_content.push_str(if comment_node.is_dev { "//" } else { "//!" });
+    hsml_content.push_str(&comment_node.text);
+
+    hsml_content
+}
+
+fn format_attribute_nodes(
+    attribute_nodes: &[HsmlNode],
+    head_width: usize,
+    indent_level: usize,
+    options: &HsmlFormatOptions,
+) -> Result<String, FormatError> {
+    let mut should_wrap = false;
+    let mut attributes: Vec<String> = vec![];
+
+    for node in attribute_nodes {
+        match node {
+            HsmlNode::Attribute(attribute_node) => {
+                // multiline values can only be kept readable when every attribute has its own line
+                should_wrap |= attribute_node
+                    .value
+                    .as_ref()
+                    .is_some_and(|value| value.contains('\n'));
+
+                attributes.push(format_attribute_node(attribute_node));
+            }
+            HsmlNode::Comment(comment_node) => {
+                // a dev comment always runs until the end of the line
+                should_wrap = true;
+
+                attributes.push(format_comment_node(comment_node));
+            }
+            _ => return Err(FormatError::unexpected_node(node, NodeContext::Attributes)),
+        }
+    }
+
+    let inline_attributes = format!("({})", attributes.join(" "));
+
+    if !should_wrap && head_width + inline_attributes.len() <= options.print_width {
+        return Ok(inline_attributes);
+    }
+
+    let indent_string = options.indent_string.repeat(indent_level);
+    let attribute_indent_string = options.indent_string.repeat(indent_level + 1);
+
+    let mut hsml_content = String::from("(\n");
+
+    for attribute in attributes {
+        hsml_content.push_str(&attribute_indent_string);
+        hsml_content.push_str(&attribute);
+        hsml_content.push('\n');
+    }
+
+    hsml_content.push_str(&indent_string);
+    hsml_content.push(')');
+
+    Ok(hsml_content)
+}
+
+fn format_tag_node(
+    tag_node: &TagNode,
+    indent_level: usize,
+    options: &HsmlFormatOptions,
+) -> Result<String, FormatError> {
+    let indent_string = options.indent_string.repeat(indent_level);
+
+    let mut hsml_content = String::new();
+
+    hsml_content.push_str(&indent_string);
+
+    // `div` is implied when the tag starts with an id or a class
+    let is_implicit_div =
+        tag_node.tag == "div" && (tag_node.id.is_some() || tag_node.classes.is_some());
+
+    if !is_implicit_div {
+        hsml_content.push_str(&tag_node.tag);
+    }
+
+    if let Some(id_node) = &tag_node.id {
+        hsml_content.push('#');
+        hsml_content.push_str(&id_node.id);
+    }
+
+    if let Some(class_nodes) = &tag_node.classes {
+        for class_node in class_nodes {
+            hsml_content.push('.');
+            hsml_content.push_str(&class_node.name);
+        }
+    }
+
+    if let Some(attribute_nodes) = &tag_node.attributes {
+        let attributes =
+            format_attribute_nodes(attribute_nodes, hsml_content.len(), indent_level, options)?;
+        hsml_content.push_str(&attributes);
+    }
+
+    if let Some(text_node) = &tag_node.text {
+        if text_node.text.contains('\n') {
+            // multiline text is written as a text block
+            let text_indent_string = options.indent_string.repeat(indent_level + 1);
+
+            hsml_content.push('.');
+
+            for line in text_node.text.lines() {
+                hsml_content.push('\n');
+
+                if !line.is_empty() {
+                    hsml_content.push_str(&text_indent_string);
+                    hsml_content.push_str(line);
+                }
+            }
+        } else if !text_node.text.is_empty() {
+            hsml_content.push(' ');
+            hsml_content.push_str(&text_node.text);
+        }
+    }
+
+    // keep a block expansion (`li: a Home`) on a single line,
+    // ASTs without spans like ones built in code have line 0 everywhere and keep the nested layout
+    if let Some([HsmlNode::Tag(child_tag_node)]) = tag_node.children.as_deref()
+        && tag_node.text.is_none()
+        && tag_node.tag_span.start.line > 0
+        && child_tag_node.span.start.line == tag_node.tag_span.start.line
+    {
+        let child_content = format_tag_node(child_tag_node, indent_level, options)?;
+
+        hsml_content.push_str(": ");
+        hsml_content.push_str(&child_content[indent_string.len()..]);
+
+        return Ok(hsml_content);
+    }
+
+    hsml_content.push('\n');
+
+    if let Some(child_nodes) = &tag_node.children {
+        for child_node in child_nodes {
+            hsml_content.push_str(&format_node(child_node, indent_level + 1, options)?);
+        }
+    }
+
+    Ok(hsml_content)
+}
+
+fn format_node(
+    node: &HsmlNode,
+    indent_level: usize,
+    options: &HsmlFormatOptions,
+) -> Result<String, FormatError> {
+    let hsml_content = match node {
+        HsmlNode::Tag(tag_node) => format_tag_node(tag_node, indent_level, options)?,
+        HsmlNode::Comment(comment_node) => {
+            let mut hsml_content = options.indent_string.repeat(indent_level);
+            hsml_content.push_str(&format_comment_node(comment_node));
+            hsml_content.push('\n');
+            hsml_content
+        }
+        HsmlNode::Text(text_node) => {
+            let mut hsml_content = options.indent_string.repeat(indent_level);
+            hsml_content.push('|');
+
+            if !text_node.text.is_empty() {
+                hsml_content.push(' ');
+                hsml_content.push_str(&text_node.text);
+            }
+
+            hsml_content.push('\n');
+            hsml_content
+        }
+        HsmlNode::Doctype(doctype_node) => {
+            let mut hsml_content = String::from("doctype");
+
+            if !doctype_node.value.is_empty() {
+                hsml_content.push(' ');
+                hsml_content.push_str(&doctype_node.value);
+            }
+
+            hsml_content.push('\n');
+            hsml_content
+        }
+        HsmlNode::Error(error_node) => {
+            // keep source that could not be parsed as it is
+            let mut hsml_content = options.indent_string.repeat(indent_level);
+            hsml_content.push_str(&error_node.text);
+            hsml_content.push('\n');
+            hsml_content
+        }
+        _ => {
+            let context = if indent_level == 0 {
+                NodeContext::Root
+            } else {
+                NodeContext::Children
+            };
+
+            return Err(FormatError::unexpected_node(node, context));
+        }
+    };
+
+    Ok(hsml_content)
+}
+
+pub fn format(hsml_ast: &RootNode, options: &HsmlFormatOptions) -> Result<String, FormatError> {
+    let mut hsml_content = String::new();
+
+    for node in &hsml_ast.nodes {
+        hsml_content.push_str(&format_node(node, 0, options)?);
+    }
+
+    Ok(hsml_content)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        formatter::{HsmlFormatOptions, format},
+        parser::{HsmlNode, parse::parse, strip_spans},
+    };
+
+    fn format_source(input: &str, options: &HsmlFormatOptions) -> String {
+        let (rest, ast) = parse(input).unwrap();
+        assert_eq!(rest, "");
+
+        format(&ast, options).unwrap()
+    }
+
+    #[test]
+    fn it_should_format_indentation_and_attribute_spacing() {
+        let input = r#"
+
+div.card
+    //! a native comment
+    img(  src="/fancy-avatar.jpg",alt="Fancy Avatar"  )
+    p.text-lg.
+        "Tailwind CSS is the only framework
+        that I've seen scale on large teams."
+    div#main text
+"#;
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            r#".card
+  //! a native comment
+  img(src="/fancy-avatar.jpg" alt="Fancy Avatar")
+  p.text-lg.
+    "Tailwind CSS is the only framework
+    that I've seen scale on large teams."
+  #main text
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_format_doctype() {
+        let input = "doctype   html  \nhtml\n";
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            "doctype html\nhtml\n"
+        );
+    }
+
+    #[test]
+    fn it_should_format_piped_text() {
+        let input = "p\n    |   Click\n    a here\n    |\n";
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            "p\n  |   Click\n  a here\n  |\n"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_block_expansion() {
+        let input = "ul\n  li.md:flex:   a(href=\"/\") Home\n  li: a: span\n    b bold\n";
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            "ul\n  li.md:flex: a(href=\"/\") Home\n  li: a: span\n    b bold\n"
+        );
+    }
+
+    #[test]
+    fn it_should_nest_single_child_without_spans() {
+        let (_, mut ast) = parse("ul\n  li: a Home\n").unwrap();
+        strip_spans(&mut ast.nodes);
+
+        assert_eq!(
+            format(&ast, &HsmlFormatOptions::default()).unwrap(),
+            "ul\n  li\n    a Home\n"
+        );
+    }
+
+    #[test]
+    fn it_should_quote_unquoted_attribute_values() {
+        let input = "button(type=button, tabindex=-1) Close\n";
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            "button(type=\"button\" tabindex=\"-1\") Close\n"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_single_quoted_attribute_values() {
+        let input = "a(title='He said \"hi\"' href='/')\n";
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            "a(title='He said \"hi\"' href='/')\n"
+        );
+    }
+
+    #[test]
+    fn it_should_keep_escaped_quotes_in_attribute_values() {
+        let input = "abbr(title=\"say \\\"hi\\\"\" data-x='it\\'s') hi\n";
+
+        assert_eq!(format_source(input, &HsmlFormatOptions::default()), input);
+    }
+
+    #[test]
+    fn it_should_escape_backslashes_when_quoting_attribute_values() {
+        let input = r#"div(x=a\ y="C:\\dir\\" z='it\'s')
+"#;
+
+        let formatted = format_source(input, &HsmlFormatOptions::default());
+
+        assert_eq!(
+            formatted,
+            r#"div(x="a\\" y="C:\\dir\\" z='it\'s')
+"#
+        );
+
+        // unquoted values get quotes, but the parsed values stay the same
+        let attribute_values = |source: &str| {
+            let (_, ast) = parse(source).unwrap();
+            let HsmlNode::Tag(tag_node) = &ast.nodes[0] else {
+                panic!("expected a tag node");
+            };
+
+            tag_node
+                .attributes
+                .iter()
+                .flatten()
+                .map(|node| match node {
+                    HsmlNode::Attribute(attribute_node) => attribute_node.value.clone(),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(attribute_values(&formatted), attribute_values(input));
+        assert_eq!(
+            attribute_values(input),
+            vec![
+                Some(String::from(r"a\")),
+                Some(String::from(r"C:\dir\")),
+                Some(String::from("it's")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_wrap_long_attribute_lists() {
+        let input = r#"img.rounded-full.mx-auto(src="/fancy-avatar.jpg" alt="A fancy avatar" width="384" height="512")
+"#;
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            r#"img.rounded-full.mx-auto(
+  src="/fancy-avatar.jpg"
+  alt="A fancy avatar"
+  width="384"
+  height="512"
+)
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_unwrap_short_attribute_lists() {
+        let input = r#"img(
+    src="/fancy-avatar.jpg"
+    alt="Fancy Avatar"
+)
+"#;
+
+        assert_eq!(
+            format_source(input, &HsmlFormatOptions::default()),
+            r#"img(src="/fancy-avatar.jpg" alt="Fancy Avatar")
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_keep_attribute_comments_on_their_own_line() {
+        let input = r#"div
+    img(
+        // supports attribute inline comments
+        src="/fancy-avatar.jpg"
+    )
+"#;
+
+        assert_eq!(
+            format_source(
+                input,
+                &HsmlFormatOptions {
+                    indent_string: String::from("\t"),
+                    ..HsmlFormatOptions::default()
+                }
+            ),
+            "div\n\timg(\n\t\t// supports attribute inline comments\n\t\tsrc=\"/fancy-avatar.jpg\"\n\t)\n"
+        );
+    }
+
+    #[test]
+    fn it_should_be_idempotent() {
+        let input = r#".space-y-3(
+  :class="{
+    'pt2 pb0.5 px3.5 bg-dm rounded-4 me--1': isDM,
+    'ms--3.5 mt--1 ms--1': isDM && context !== 'details',
+  }"
+)
+  StatusBody(v-if="(!isFiltered && isSensitiveNonSpoiler) || hideAllMedia" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''")
+  StatusSpoiler(:enabled="hasSpoilerOrSensitiveMedia || isFiltered" :filter="isFiltered")
+    template(v-if="spoilerTextPresent" #spoiler)
+      p {{ status.spoilerText }}
+    div(v-if="isDM")
+"#;
+
+        let options = HsmlFormatOptions::default();
+
+        let formatted = format_source(input, &options);
+
+        assert_eq!(format_source(&formatted, &options), formatted);
+
+        let (_, mut formatted_ast) = parse(&formatted).unwrap();
+        let (_, mut ast) = parse(input).unwrap();
+        strip_spans(&mut formatted_ast.nodes);
+        strip_spans(&mut ast.nodes);
+
+        assert_eq!(formatted_ast, ast);
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_return_error_for_unexpected_nodes() {
+        let (_, mut ast) = parse("div.a(b)\n").unwrap();
+        let HsmlNode::Tag(tag_node) = &mut ast.nodes[0] else {
+            unreachable!();
+        };
+        let class_node = tag_node.classes.take().unwrap().remove(0);
+        tag_node.children = Some(vec![HsmlNode::Class(class_node)]);
+
+        let err = format(&ast, &HsmlFormatOptions::default()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "error: Unexpected class node in the children of a tag at 1:4"
+        );
+
+        let HsmlNode::Tag(tag_node) = &mut ast.nodes[0] else {
+            unreachable!();
+        };
+        let class_node = tag_node.children.take().unwrap().remove(0);
+        tag_node.attributes = Some(vec![class_node]);
+
+        let err = format(&ast, &HsmlFormatOptions::default()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "error: Unexpected class node in the attributes of a tag at 1:4"
+        );
+    }
+}
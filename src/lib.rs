@@ -1,11 +1,49 @@
+pub mod build;
 pub mod compiler;
+pub mod formatter;
+pub mod linter;
 pub mod parser;
 
 use wasm_bindgen::prelude::*;
 
+/// Compiles the content, parse and compile errors are thrown as JavaScript `Error`.
 #[wasm_bindgen]
-pub fn compile_content(source: &str) -> String {
-    let (_, ast) = parser::parse::parse(source).unwrap();
+pub fn compile_content(source: &str) -> Result<String, JsError> {
+    let (_, ast) = parser::parse::parse(source)?;
 
-    compiler::compile(&ast, &compiler::HsmlCompileOptions::default())
+    Ok(compiler::compile(
+        &ast,
+        &compiler::HsmlCompileOptions::default(),
+    )?)
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct CompileOutput {
+    pub html: String,
+    /// Source Map v3 as JSON, so bundler plugins can chain it with their own maps.
+    pub map: String,
+}
+
+/// Compiles the content and returns the HTML together with its source map.
+///
+/// `file` is the name of the compiled file and `source` the name of the `.hsml` file, as they should appear in the map.
+#[wasm_bindgen]
+pub fn compile_content_with_source_map(
+    content: &str,
+    file: &str,
+    source: &str,
+) -> Result<CompileOutput, JsError> {
+    let (_, ast) = parser::parse::parse(content)?;
+
+    let (html, source_map) = compiler::compile_with_source_map(
+        &ast,
+        &compiler::HsmlCompileOptions::default(),
+        file,
+        source,
+    )?;
+
+    Ok(CompileOutput {
+        html,
+        map: source_map.to_json(),
+    })
 }
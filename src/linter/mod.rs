@@ -0,0 +1,139 @@
+use std::{collections::HashMap, fmt};
+
+use serde::Deserialize;
+
+use crate::parser::{RootNode, span::Span};
+
+use self::rules::RULES;
+
+pub mod rules;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Off => write!(f, "off"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+/// Configures the severity of each rule, rules not listed keep their default severity.
+///
+/// Can be deserialized from JSON like `{ "rules": { "empty-class": "off" } }`.
+#[derive(Debug, Default, Deserialize)]
+pub struct HsmlLintOptions {
+    #[serde(default)]
+    pub rules: HashMap<String, Severity>,
+}
+
+impl HsmlLintOptions {
+    /// Returns the first configured rule id that does not exist.
+    pub fn unknown_rule(&self) -> Option<&str> {
+        self.rules
+            .keys()
+            .find(|id| !RULES.iter().any(|rule| rule.id == id.as_str()))
+            .map(String::as_str)
+    }
+}
+
+pub fn lint(source: &str, hsml_ast: &RootNode, options: &HsmlLintOptions) -> Vec<LintDiagnostic> {
+    let mut diagnostics: Vec<LintDiagnostic> = vec![];
+
+    for rule in RULES {
+        let severity = options
+            .rules
+            .get(rule.id)
+            .copied()
+            .unwrap_or(rule.default_severity);
+
+        if severity == Severity::Off {
+            continue;
+        }
+
+        for finding in (rule.check)(source, hsml_ast) {
+            diagnostics.push(LintDiagnostic {
+                rule: rule.id,
+                severity,
+                message: finding.message,
+                span: finding.span,
+            });
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::{HsmlLintOptions, LintDiagnostic, Severity, lint},
+        parser::{parse::parse, span::LineIndex},
+    };
+
+    #[test]
+    fn it_should_report_findings_with_default_severity() {
+        let input = r#"div#main(id="other")
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+        let line_index = LineIndex::new(input);
+
+        assert_eq!(
+            lint(input, &ast, &HsmlLintOptions::default()),
+            vec![LintDiagnostic {
+                rule: "id-conflict",
+                severity: Severity::Error,
+                message: String::from(
+                    r##"Attribute "id" conflicts with the id shorthand "#main""##
+                ),
+                span: line_index.span(9, 19),
+            }]
+        );
+    }
+
+    #[test]
+    fn it_should_respect_configured_severity() {
+        let input = r#"div#main(id="other")
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let options: HsmlLintOptions =
+            serde_json::from_str(r#"{ "rules": { "id-conflict": "warning" } }"#).unwrap();
+
+        let diagnostics = lint(input, &ast, &options);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+        let options: HsmlLintOptions =
+            serde_json::from_str(r#"{ "rules": { "id-conflict": "off" } }"#).unwrap();
+
+        assert_eq!(lint(input, &ast, &options), vec![]);
+    }
+
+    #[test]
+    fn it_should_detect_unknown_rules() {
+        let options: HsmlLintOptions =
+            serde_json::from_str(r#"{ "rules": { "no-such-rule": "off" } }"#).unwrap();
+
+        assert_eq!(options.unknown_rule(), Some("no-such-rule"));
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+
+use crate::parser::{
+    HsmlNode, RootNode,
+    attribute::node::AttributeNode,
+    span::{LineIndex, Span},
+    tag::node::TagNode,
+};
+
+use super::Severity;
+
+pub struct LintFinding {
+    pub message: String,
+    pub span: Span,
+}
+
+pub struct LintRule {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub default_severity: Severity,
+    pub check: fn(source: &str, hsml_ast: &RootNode) -> Vec<LintFinding>,
+}
+
+pub const RULES: &[LintRule] = &[
+    LintRule {
+        id: "duplicate-attribute",
+        description: "Disallow the same attribute key more than once on a tag",
+        default_severity: Severity::Error,
+        check: check_duplicate_attribute,
+    },
+    LintRule {
+        id: "id-conflict",
+        description: "Disallow an `id` attribute on a tag that already has an `#id` shorthand",
+        default_severity: Severity::Error,
+        check: check_id_conflict,
+    },
+    LintRule {
+        id: "empty-class",
+        description: "Disallow empty class names like in `div.`",
+        default_severity: Severity::Warning,
+        check: check_empty_class,
+    },
+    LintRule {
+        id: "mixed-indentation",
+        description: "Disallow mixing tabs and spaces for indentation",
+        default_severity: Severity::Error,
+        check: check_mixed_indentation,
+    },
+];
+
+/// Calls `f` for every tag node in the tree.
+fn walk_tag_nodes(nodes: &[HsmlNode], f: &mut dyn FnMut(&TagNode)) {
+    for node in nodes {
+        if let HsmlNode::Tag(tag_node) = node {
+            f(tag_node);
+
+            if let Some(child_nodes) = &tag_node.children {
+                walk_tag_nodes(child_nodes, f);
+            }
+        }
+    }
+}
+
+fn attribute_nodes(tag_node: &TagNode) -> impl Iterator<Item = &AttributeNode> {
+    tag_node
+        .attributes
+        .iter()
+        .flatten()
+        .filter_map(|node| match node {
+            HsmlNode::Attribute(attribute_node) => Some(attribute_node),
+            _ => None,
+        })
+}
+
+fn check_duplicate_attribute(_source: &str, hsml_ast: &RootNode) -> Vec<LintFinding> {
+    let mut findings: Vec<LintFinding> = vec![];
+
+    walk_tag_nodes(&hsml_ast.nodes, &mut |tag_node| {
+        let mut seen_keys: Vec<&str> = vec![];
+
+        for attribute_node in attribute_nodes(tag_node) {
+            let key = attribute_node.key.as_str();
+
+            if seen_keys.contains(&key) {
+                findings.push(LintFinding {
+                    message: format!(r#"Duplicate attribute "{}""#, key),
+                    span: attribute_node.span,
+                });
+            } else {
+                seen_keys.push(key);
+            }
+        }
+    });
+
+    findings
+}
+
+fn check_id_conflict(_source: &str, hsml_ast: &RootNode) -> Vec<LintFinding> {
+    let mut findings: Vec<LintFinding> = vec![];
+
+    walk_tag_nodes(&hsml_ast.nodes, &mut |tag_node| {
+        let Some(id_node) = &tag_node.id else {
+            return;
+        };
+
+        for attribute_node in attribute_nodes(tag_node).filter(|node| node.key == "id") {
+            findings.push(LintFinding {
+                message: format!(
+                    r##"Attribute "id" conflicts with the id shorthand "#{}""##,
+                    id_node.id
+                ),
+                span: attribute_node.span,
+            });
+        }
+    });
+
+    findings
+}
+
+fn check_empty_class(_source: &str, hsml_ast: &RootNode) -> Vec<LintFinding> {
+    let mut findings: Vec<LintFinding> = vec![];
+
+    walk_tag_nodes(&hsml_ast.nodes, &mut |tag_node| {
+        let empty_classes = tag_node
+            .classes
+            .iter()
+            .flatten()
+            .filter(|class_node| class_node.name.is_empty());
+
+        for class_node in empty_classes {
+            findings.push(LintFinding {
+                message: String::from("Empty class name"),
+                span: class_node.span,
+            });
+        }
+    });
+
+    findings
+}
+
+fn indent_char_name(c: char) -> &'static str {
+    if c == '\t' { "tabs" } else { "spaces" }
+}
+
+/// Collects the lines on which nodes start, text blocks and multiline attribute values are not part of the structure.
+///
+/// Lines of child nodes map to `true`, the parser already rejects their indentation when it mixes tabs and spaces.
+fn collect_node_lines(nodes: &[HsmlNode], is_child: bool, lines: &mut BTreeMap<usize, bool>) {
+    for node in nodes {
+        let span = match node {
+            HsmlNode::Root(root_node) => {
+                collect_node_lines(&root_node.nodes, true, lines);
+                continue;
+            }
+            HsmlNode::Doctype(doctype_node) => doctype_node.span,
+            HsmlNode::Tag(tag_node) => {
+                let attribute_nodes = tag_node.attributes.as_deref().unwrap_or_default();
+                let child_nodes = tag_node.children.as_deref().unwrap_or_default();
+
+                collect_node_lines(attribute_nodes, false, lines);
+                collect_node_lines(child_nodes, true, lines);
+                tag_node.span
+            }
+            HsmlNode::Comment(comment_node) => comment_node.span,
+            HsmlNode::Id(id_node) => id_node.span,
+            HsmlNode::Class(class_node) => class_node.span,
+            HsmlNode::Attribute(attribute_node) => attribute_node.span,
+            HsmlNode::Text(text_node) => text_node.span,
+            HsmlNode::Error(error_node) => error_node.span,
+        };
+
+        *lines.entry(span.start.line).or_default() |= is_child;
+    }
+}
+
+fn check_mixed_indentation(source: &str, hsml_ast: &RootNode) -> Vec<LintFinding> {
+    let mut findings: Vec<LintFinding> = vec![];
+
+    let line_index = LineIndex::new(source);
+
+    let mut node_lines = BTreeMap::new();
+    collect_node_lines(&hsml_ast.nodes, true, &mut node_lines);
+
+    // the first indented line decides which character is used for indentation
+    let mut indent_char: Option<char> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let indentation: &str = &line[..line.len() - line.trim_start().len()];
+
+        let Some(&is_child_line) = node_lines.get(&(index + 1)) else {
+            continue;
+        };
+
+        if indentation.is_empty() {
+            continue;
+        }
+
+        let line_start = line_index
+            .offset(index + 1, 1)
+            .expect("line should exist in source");
+        let span = line_index.span(line_start, line_start + indentation.len());
+
+        if indentation.contains('\t') && indentation.contains(' ') {
+            // reported by the parser as E0004
+            if is_child_line {
+                continue;
+            }
+
+            findings.push(LintFinding {
+                message: String::from("Indentation mixes tabs and spaces"),
+                span,
+            });
+            continue;
+        }
+
+        let line_indent_char = if indentation.contains('\t') {
+            '\t'
+        } else {
+            ' '
+        };
+
+        match indent_char {
+            None => indent_char = Some(line_indent_char),
+            Some(c) if c != line_indent_char => {
+                findings.push(LintFinding {
+                    message: format!(
+                        "Indented with {} but the file is indented with {}",
+                        indent_char_name(line_indent_char),
+                        indent_char_name(c),
+                    ),
+                    span,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::rules::{
+            check_duplicate_attribute, check_empty_class, check_id_conflict,
+            check_mixed_indentation,
+        },
+        parser::{
+            parse::{parse, parse_recovering},
+            span::LineIndex,
+        },
+    };
+
+    #[test]
+    fn it_should_check_duplicate_attribute() {
+        let input = r#"div
+  img(src="a.png" alt="" src="b.png")
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+        let line_index = LineIndex::new(input);
+
+        let findings = check_duplicate_attribute(input, &ast);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, r#"Duplicate attribute "src""#);
+        assert_eq!(findings[0].span, line_index.span(29, 40));
+    }
+
+    #[test]
+    fn it_should_check_id_conflict() {
+        let input = r#"section#intro
+  p#text(id="other") Hello
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+
+        let findings = check_id_conflict(input, &ast);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            (findings[0].span.start.line, findings[0].span.start.column),
+            (2, 10)
+        );
+    }
+
+    #[test]
+    fn it_should_check_empty_class() {
+        let input = r#"div.card. Hello
+"#;
+
+        let (_, ast) = parse(input).unwrap();
+        let line_index = LineIndex::new(input);
+
+        let findings = check_empty_class(input, &ast);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, "Empty class name");
+        assert_eq!(findings[0].span, line_index.span(8, 9));
+    }
+
+    #[test]
+    fn it_should_check_mixed_indentation() {
+        let input = "div\n  p(\n\tsrc=\"a.png\"\n \talt=\"\"\n  )\n";
+
+        let (_, ast) = parse(input).unwrap();
+        let line_index = LineIndex::new(input);
+
+        let findings = check_mixed_indentation(input, &ast);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].message,
+            "Indented with tabs but the file is indented with spaces"
+        );
+        assert_eq!(findings[0].span, line_index.span(9, 10));
+        assert_eq!(findings[1].message, "Indentation mixes tabs and spaces");
+        assert_eq!(findings[1].span, line_index.span(22, 24));
+    }
+
+    #[test]
+    fn it_should_not_check_indentation_of_text_blocks() {
+        let input = "div\n  pre.\n    line\n    \tcode\n  p(title=\"a\n\tb\")\n";
+
+        let (_, ast) = parse(input).unwrap();
+
+        let findings = check_mixed_indentation(input, &ast);
+
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn it_should_leave_mixed_indentation_of_child_lines_to_the_parser() {
+        let input = "div\n \tspan skipped\n  p ok\n";
+
+        let (ast, errors) = parse_recovering(input);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), "E0004");
+        assert!(check_mixed_indentation(input, &ast).is_empty());
+    }
+}
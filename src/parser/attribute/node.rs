@@ -1,38 +1,113 @@
 use nom::{
-    IResult,
     bytes::complete::{tag, take_till},
+    error::{Error, ErrorKind},
 };
+use serde::Serialize;
 
-use crate::parser::{HsmlNode, HsmlProcessContext, comment::node::comment_dev_node};
+use crate::parser::{
+    HsmlNode, HsmlProcessContext, HsmlResult, comment::node::comment_dev_node,
+    error::HsmlParseError, span::Span,
+};
+
+use super::process::{process_attribute, unescape_attribute_value};
+
+/// How the value of an attribute was written in the source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeQuote {
+    #[default]
+    Double,
+    Single,
+    Unquoted,
+}
 
-use super::process::process_attribute;
+impl AttributeQuote {
+    /// The quote char, `None` for unquoted values.
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            AttributeQuote::Double => Some('"'),
+            AttributeQuote::Single => Some('\''),
+            AttributeQuote::Unquoted => None,
+        }
+    }
+}
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct AttributeNode {
     pub key: String,
     pub value: Option<String>,
+    /// Only meaningful if there is a value.
+    pub quote: AttributeQuote,
+    /// Span of the whole attribute including its value.
+    pub span: Span,
+    pub key_span: Span,
+    /// Span of the value without the surrounding quotes.
+    pub value_span: Option<Span>,
 }
 
 pub fn attribute_node<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, AttributeNode> {
-    let (input, attribute) = process_attribute(input, context)?;
+) -> HsmlResult<'a, AttributeNode> {
+    let start = context.offset(input);
+
+    let (input, attribute) = process_attribute(input, context).map_err(|err| {
+        let is_key_error = matches!(
+            &err,
+            nom::Err::Error(Error {
+                code: ErrorKind::AlphaNumeric,
+                ..
+            })
+        );
+
+        context.map_error(err, |remaining, span| {
+            if is_key_error || remaining.starts_with(['[', '(']) {
+                // the key is invalid or contains an unclosed bracket or brace
+                HsmlParseError::InvalidAttributeKey { span }
+            } else if remaining.starts_with(['"', '\'']) {
+                HsmlParseError::UnterminatedAttributeValue { span }
+            } else {
+                HsmlParseError::InvalidAttributeValue { span }
+            }
+        })
+    })?;
 
     let equal_sign_index = attribute.find('=').unwrap_or(attribute.len());
     let (key, value) = attribute.split_at(equal_sign_index);
 
     // Remove surrounding quotes and leading `=` from value
-    let value = value
-        .strip_prefix(r#"=""#)
-        .and_then(|v| v.strip_suffix('"'))
-        .map(|v| v.to_string());
+    let (value, quote) = match value.strip_prefix('=') {
+        Some(value) if value.starts_with('"') => (
+            value.strip_prefix('"').and_then(|v| v.strip_suffix('"')),
+            AttributeQuote::Double,
+        ),
+        Some(value) if value.starts_with('\'') => (
+            value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')),
+            AttributeQuote::Single,
+        ),
+        Some(value) => (Some(value), AttributeQuote::Unquoted),
+        None => (None, AttributeQuote::default()),
+    };
+
+    // the value starts after `="`, `='` or `=` if it is unquoted
+    let value_span = value.map(|value| {
+        let quote_len = quote.as_char().map_or(0, char::len_utf8);
+        let value_start = start + equal_sign_index + 1 + quote_len;
+        context.span(value_start, value_start + value.len())
+    });
 
     Ok((
         input,
         AttributeNode {
             key: key.to_string(),
-            value,
+            value: value.map(|v| match quote.as_char() {
+                Some(quote) => unescape_attribute_value(v, quote),
+                None => v.to_string(),
+            }),
+            quote,
+            span: context.span(start, start + attribute.len()),
+            key_span: context.span(start, start + key.len()),
+            value_span,
         },
     ))
 }
@@ -40,8 +115,12 @@ pub fn attribute_node<'a>(
 pub fn attribute_nodes<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, Vec<HsmlNode>> {
-    let (mut input, _) = tag("(")(input)?;
+) -> HsmlResult<'a, Vec<HsmlNode>> {
+    let start = context.offset(input);
+
+    let (mut input, _) = tag::<_, _, Error<&str>>("(")(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
 
     let mut nodes: Vec<HsmlNode> = vec![];
 
@@ -50,7 +129,18 @@ pub fn attribute_nodes<'a>(
     // collect attr
     // if attr is empty, break
     loop {
-        let (remaining, _) = take_till(|c: char| !c.is_whitespace() && c != ',')(input)?;
+        let (remaining, _) =
+            take_till::<_, _, Error<&str>>(|c: char| !c.is_whitespace() && c != ',')(input)
+                .map_err(|err| {
+                    context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+                })?;
+
+        if remaining.is_empty() {
+            // the attribute list was never closed
+            return Err(nom::Err::Failure(HsmlParseError::UnclosedAttributeList {
+                span: context.span(start, start + 1),
+            }));
+        }
 
         if remaining.starts_with(')') {
             input = remaining;
@@ -59,7 +149,7 @@ pub fn attribute_nodes<'a>(
 
         // if remaining starts with `//`, it is a dev comment
         if remaining.starts_with("//") {
-            let (remaining, comment) = comment_dev_node(remaining)?;
+            let (remaining, comment) = comment_dev_node(remaining, context)?;
             nodes.push(HsmlNode::Comment(comment));
 
             input = remaining;
@@ -72,7 +162,8 @@ pub fn attribute_nodes<'a>(
         input = remaining;
     }
 
-    let (input, _) = tag(")")(input)?;
+    // the loop only breaks on a `)`
+    let input = &input[1..];
 
     Ok((input, nodes))
 }
@@ -81,21 +172,26 @@ pub fn attribute_nodes<'a>(
 mod tests {
     use crate::parser::{
         HsmlNode, HsmlProcessContext,
-        attribute::node::{AttributeNode, attribute_node, attribute_nodes},
+        attribute::node::{AttributeNode, AttributeQuote, attribute_node, attribute_nodes},
         comment::node::CommentNode,
     };
 
     #[test]
     fn it_should_return_attribute_node() {
-        let mut context = HsmlProcessContext::default();
+        let input = r#"key="value""#;
+        let mut context = HsmlProcessContext::new(input);
 
-        let (input, attribute) = attribute_node(r#"key="value""#, &mut context).unwrap();
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
 
         assert_eq!(
             attribute,
             AttributeNode {
                 key: String::from("key"),
-                value: Some(String::from("value"))
+                value: Some(String::from("value")),
+                quote: AttributeQuote::Double,
+                span: context.span(0, 11),
+                key_span: context.span(0, 3),
+                value_span: Some(context.span(5, 10)),
             }
         );
 
@@ -103,18 +199,81 @@ mod tests {
     }
 
     #[test]
-    fn it_should_return_attribute_node_with_multiline() {
-        let mut context = HsmlProcessContext::default();
+    fn it_should_return_boolean_attribute_node() {
+        let input = "disabled)";
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            attribute,
+            AttributeNode {
+                key: String::from("disabled"),
+                value: None,
+                quote: AttributeQuote::Double,
+                span: context.span(0, 8),
+                key_span: context.span(0, 8),
+                value_span: None,
+            }
+        );
+
+        assert_eq!(input, ")");
+    }
+
+    #[test]
+    fn it_should_return_unquoted_attribute_node() {
+        let input = "tabindex=-1)";
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            attribute,
+            AttributeNode {
+                key: String::from("tabindex"),
+                value: Some(String::from("-1")),
+                quote: AttributeQuote::Unquoted,
+                span: context.span(0, 11),
+                key_span: context.span(0, 8),
+                value_span: Some(context.span(9, 11)),
+            }
+        );
+
+        assert_eq!(input, ")");
+    }
+
+    #[test]
+    fn it_should_return_single_quoted_attribute_node() {
+        let input = r#"title='He said "hi"')"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            attribute,
+            AttributeNode {
+                key: String::from("title"),
+                value: Some(String::from(r#"He said "hi""#)),
+                quote: AttributeQuote::Single,
+                span: context.span(0, 20),
+                key_span: context.span(0, 5),
+                value_span: Some(context.span(7, 19)),
+            }
+        );
+
+        assert_eq!(input, ")");
+    }
 
-        let (input, attribute) = attribute_node(
-            r#"class="{
+    #[test]
+    fn it_should_return_attribute_node_with_multiline() {
+        let input = r#"class="{
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"
-    :key="item.id""#,
-            &mut context,
-        )
-        .unwrap();
+    :key="item.id""#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
 
         assert_eq!(
             attribute,
@@ -125,10 +284,17 @@ mod tests {
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"#
-                ))
+                )),
+                quote: AttributeQuote::Double,
+                span: context.span(0, 81),
+                key_span: context.span(0, 5),
+                value_span: Some(context.span(7, 80)),
             }
         );
 
+        let value_span = attribute.value_span.unwrap();
+        assert_eq!((value_span.end.line, value_span.end.column), (4, 6));
+
         assert_eq!(
             input,
             r#"
@@ -138,21 +304,29 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes() {
-        let mut context = HsmlProcessContext::default();
+        let input = r#"(key="value", :key2="value2")"#;
+        let mut context = HsmlProcessContext::new(input);
 
-        let (input, attribute_nodes) =
-            attribute_nodes(r#"(key="value", :key2="value2")"#, &mut context).unwrap();
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
             vec![
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
-                    value: Some(String::from("value"))
+                    value: Some(String::from("value")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(1, 12),
+                    key_span: context.span(1, 4),
+                    value_span: Some(context.span(6, 11)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
-                    value: Some(String::from("value2"))
+                    value: Some(String::from("value2")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(14, 28),
+                    key_span: context.span(14, 19),
+                    value_span: Some(context.span(21, 27)),
                 })
             ]
         );
@@ -162,28 +336,33 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_wrapped() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attribute_nodes) = attribute_nodes(
-            r#"(
+        let input = r#"(
     key="value"
     :key2="value2"
 )
-"#,
-            &mut context,
-        )
-        .unwrap();
+"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
             vec![
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
-                    value: Some(String::from("value"))
+                    value: Some(String::from("value")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(6, 17),
+                    key_span: context.span(6, 9),
+                    value_span: Some(context.span(11, 16)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
-                    value: Some(String::from("value2"))
+                    value: Some(String::from("value2")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(22, 36),
+                    key_span: context.span(22, 27),
+                    value_span: Some(context.span(29, 35)),
                 })
             ]
         );
@@ -193,19 +372,16 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_dev_comments() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attribute_nodes) = attribute_nodes(
-            r#"(
+        let input = r#"(
     // comment 1
     key="value"
     // comment 2
     :key2="value2"
 )
-"#,
-            &mut context,
-        )
-        .unwrap();
+"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
@@ -213,18 +389,28 @@ mod tests {
                 HsmlNode::Comment(CommentNode {
                     is_dev: true,
                     text: String::from(" comment 1"),
+                    span: context.span(6, 18),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
                     value: Some(String::from("value")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(23, 34),
+                    key_span: context.span(23, 26),
+                    value_span: Some(context.span(28, 33)),
                 }),
                 HsmlNode::Comment(CommentNode {
                     is_dev: true,
                     text: String::from(" comment 2"),
+                    span: context.span(39, 51),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
                     value: Some(String::from("value2")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(56, 70),
+                    key_span: context.span(56, 61),
+                    value_span: Some(context.span(63, 69)),
                 }),
             ]
         );
@@ -234,17 +420,14 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_multiline() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attributes) = attribute_nodes(
-            r#"(class="{
+        let input = r#"(class="{
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"
-    :key="item.id")"#,
-            &mut context,
-        )
-        .unwrap();
+    :key="item.id")"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attributes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attributes,
@@ -257,10 +440,18 @@ mod tests {
         'is-disabled': isDisabled,
     }"#
                     )),
+                    quote: AttributeQuote::Double,
+                    span: context.span(1, 82),
+                    key_span: context.span(1, 6),
+                    value_span: Some(context.span(8, 81)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key"),
                     value: Some(String::from("item.id")),
+                    quote: AttributeQuote::Double,
+                    span: context.span(87, 101),
+                    key_span: context.span(87, 91),
+                    value_span: Some(context.span(93, 100)),
                 }),
             ]
         );
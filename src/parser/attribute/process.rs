@@ -28,42 +28,42 @@ fn process_attribute_key(input: &str) -> IResult<&str, &str> {
     loop {
         // get first char and check if it is a `(`
         // if so, find the closing brace, because otherwise the closing brace is the end of the attributes
-        let first_char = remaining.get(..1);
+        let first_char = remaining.chars().next();
 
         match first_char {
-            Some(")") => {
+            Some(')') => {
                 // we hit the end of the attributes, so we are done
                 break;
             }
-            Some(",") => {
+            Some(',') => {
                 // we hit a comma, so we are done
                 break;
             }
-            Some("=") => {
+            Some('=') => {
                 // we hit an equal sign, so we are done
                 break;
             }
-            Some(" ") => {
+            Some(' ') => {
                 // we hit a whitespace, so we are done
                 break;
             }
-            Some("\r") if remaining.get(1..2) == Some("\n") => {
+            Some('\r') if remaining[1..].starts_with('\n') => {
                 // we hit a newline, so we are done
                 break;
             }
-            Some("\r") => {}
-            Some("\n") => {
+            Some('\r') => {}
+            Some('\n') => {
                 // we hit a newline, so we are done
                 break;
             }
-            Some("[") => {
+            Some('[') => {
                 // find the closing bracket
                 let closing_bracket = ']';
 
                 let mut closing_bracket_index = 0;
                 let mut is_escaped = false;
 
-                for (index, c) in remaining.chars().enumerate() {
+                for (index, c) in remaining.char_indices() {
                     if index == 0 {
                         // skip first char, because it is the opening bracket
                         continue;
@@ -91,14 +91,14 @@ fn process_attribute_key(input: &str) -> IResult<&str, &str> {
 
                 continue;
             }
-            Some("(") => {
+            Some('(') => {
                 // find the closing brace
                 let closing_brace = ')';
 
                 let mut closing_brace_index = 0;
                 let mut is_escaped = false;
 
-                for (index, c) in remaining.chars().enumerate() {
+                for (index, c) in remaining.char_indices() {
                     if index == 0 {
                         // skip first char, because it is the opening brace
                         continue;
@@ -126,9 +126,9 @@ fn process_attribute_key(input: &str) -> IResult<&str, &str> {
 
                 continue;
             }
-            Some(_) => {
-                attribute_key_index += 1;
-                remaining = remaining.get(1..).unwrap();
+            Some(c) => {
+                attribute_key_index += c.len_utf8();
+                remaining = &remaining[c.len_utf8()..];
                 continue;
             }
             None => {
@@ -147,22 +147,23 @@ fn process_attribute_value<'a>(
     _context: &mut HsmlProcessContext,
 ) -> IResult<&'a str, &'a str> {
     // get first char
-    let first_char = input.chars().next().unwrap();
+    let first_char = input.chars().next();
 
     // if first char is a quote, then we need to find the closing quote and return the value in between (together with the surrounding quotes)
-    if first_char == '"' || first_char == '\'' {
+    if let Some(first_char @ ('"' | '\'')) = first_char {
         let closing_quote = if first_char == '"' { '"' } else { '\'' };
 
         let mut closing_quote_index = 0;
         let mut is_escaped = false;
 
-        for (index, c) in input.chars().enumerate() {
+        for (index, c) in input.char_indices() {
             if index == 0 {
                 // skip first char, because it is the opening quote
                 continue;
             }
 
-            if c == '\\' {
+            // an escaped backslash does not escape the next char
+            if c == '\\' && !is_escaped {
                 is_escaped = true;
                 continue;
             }
@@ -189,8 +190,42 @@ fn process_attribute_value<'a>(
         ));
     }
 
-    // otherwise it was not a valid attribute value
-    Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+    // otherwise it is an unquoted value, which ends at a whitespace, a comma or the end of the attributes
+    let value_len = input
+        .find(|c: char| c.is_whitespace() || c == ',' || c == ')')
+        .unwrap_or(input.len());
+
+    let attribute_value = &input[..value_len];
+
+    // same restrictions as for unquoted values in HTML
+    if attribute_value.is_empty() || attribute_value.contains(['"', '\'', '=', '<', '>', '`']) {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+    }
+
+    Ok((&input[value_len..], attribute_value))
+}
+
+/// Removes the backslashes of escaped quotes and backslashes, only the quote that surrounds the value needs to be escaped.
+///
+/// Other backslashes are kept as they are, e.g. in `:title="'it\'s'"` for a binding.
+pub fn unescape_attribute_value(value: &str, quote: char) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && (next == quote || next == '\\')
+        {
+            unescaped.push(next);
+            chars.next();
+            continue;
+        }
+
+        unescaped.push(c);
+    }
+
+    unescaped
 }
 
 // An attribute key can only contain a-z, A-Z, 0-9, `-`, `_`, `:`, `#`, `@`, `[`, `]`, `(`, `)`, `{`, `}`
@@ -232,7 +267,10 @@ mod tests {
 
     use crate::parser::{
         HsmlProcessContext,
-        attribute::process::{process_attribute, process_attribute_key, process_attribute_value},
+        attribute::process::{
+            process_attribute, process_attribute_key, process_attribute_value,
+            unescape_attribute_value,
+        },
     };
 
     #[test]
@@ -256,6 +294,44 @@ mod tests {
         assert_eq!(rest, "");
     }
 
+    #[test]
+    fn it_should_process_non_ascii_attribute_value() {
+        let input = r#""dé" data-ü="ö")"#;
+
+        let (rest, attribute_value) =
+            process_attribute_value(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute_value, "dé");
+        assert_eq!(rest, r#" data-ü="ö")"#);
+
+        let (rest, attribute) =
+            process_attribute(&rest[1..], &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, r#"data-ü="ö""#);
+        assert_eq!(rest, ")");
+    }
+
+    #[test]
+    fn it_should_process_unquoted_attribute_value() {
+        let input = "-1, type=button)";
+
+        let (rest, attribute_value) =
+            process_attribute_value(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute_value, "-1");
+        assert_eq!(rest, ", type=button)");
+    }
+
+    #[test]
+    fn it_should_unescape_attribute_value() {
+        assert_eq!(
+            unescape_attribute_value(r#"say \"hi\" it\'s"#, '"'),
+            r#"say "hi" it\'s"#
+        );
+        assert_eq!(unescape_attribute_value(r"it\'s", '\''), "it's");
+        assert_eq!(unescape_attribute_value(r"C:\\dir\", '"'), r"C:\dir\");
+    }
+
     #[test]
     fn it_should_process_attribute() {
         let input = r#"src="https://github.com/""#;
@@ -379,6 +455,7 @@ mod tests {
             &mut HsmlProcessContext {
                 indent_level: 1,
                 indent_string: Some(String::from("    ")),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -397,8 +474,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_process_attribute_with_unquoted_value() {
+        let input = "width=384 height=216)";
+
+        let (rest, attribute) =
+            process_attribute(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, "width=384");
+        assert_eq!(rest, " height=216)");
+    }
+
     // Negative tests
 
+    #[test]
+    fn it_should_not_process_empty_unquoted_attribute_value() {
+        let input = "key= value)";
+
+        assert_eq!(
+            Err(nom::Err::Error(Error {
+                input: " value)",
+                code: ErrorKind::Tag
+            })),
+            process_attribute(input, &mut HsmlProcessContext::default())
+        );
+    }
+
+    #[test]
+    fn it_should_not_process_unquoted_attribute_value_with_quote() {
+        let input = "key=val\"ue)";
+
+        assert_eq!(
+            Err(nom::Err::Error(Error {
+                input: "val\"ue)",
+                code: ErrorKind::Tag
+            })),
+            process_attribute(input, &mut HsmlProcessContext::default())
+        );
+    }
+
     #[test]
     fn it_should_not_process_attribute_with_number() {
         let input = r#"1src="https://github.com""#;
@@ -452,12 +566,12 @@ mod tests {
     }
 
     #[test]
-    fn it_should_not_process_attribute_without_quoted_value() {
-        let input = "src=imgSrc";
+    fn it_should_not_process_attribute_with_invalid_unquoted_value() {
+        let input = "src==imgSrc";
 
         assert_eq!(
             Err(nom::Err::Error(Error {
-                input: "imgSrc",
+                input: "=imgSrc",
                 code: ErrorKind::Tag
             })),
             process_attribute(input, &mut HsmlProcessContext::default())
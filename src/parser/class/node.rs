@@ -1,19 +1,31 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, HsmlResult, error::HsmlParseError, span::Span};
 
 use super::process::process_class;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct ClassNode {
     pub name: String,
+    pub span: Span,
 }
 
-pub fn class_node(input: &str) -> IResult<&str, ClassNode> {
-    let (input, class_name) = process_class(input)?;
+pub fn class_node<'a>(input: &'a str, context: &HsmlProcessContext) -> HsmlResult<'a, ClassNode> {
+    let (rest, class_name) = process_class(input).map_err(|err| {
+        context.map_error(err, |remaining, span| {
+            if remaining.starts_with('[') {
+                HsmlParseError::UnclosedArbitraryValue { span }
+            } else {
+                HsmlParseError::UnexpectedInput { span }
+            }
+        })
+    })?;
 
     Ok((
-        input,
+        rest,
         ClassNode {
             name: class_name.to_string(),
+            span: context.span_between(input, rest),
         },
     ))
 }
@@ -37,6 +37,11 @@ pub fn process_class(input: &str) -> IResult<&str, &str> {
                 // we hit a tab, so we are done
                 break;
             }
+            Some(":") if remaining.get(1..2) == Some(" ") => {
+                // we hit a block expansion, so we are done
+                // a colon inside a class name is always followed by the rest of the class, e.g. `md:flex`
+                break;
+            }
             Some("\r") if remaining.get(1..2) == Some("\n") => {
                 // we hit a newline, so we are done
                 break;
@@ -125,6 +130,16 @@ mod tests {
         assert_eq!(rest, " Text");
     }
 
+    #[test]
+    fn it_should_process_class_with_block_expansion() {
+        let input = ".md:flex: a Text";
+
+        let (rest, class) = process_class(input).unwrap();
+
+        assert_eq!(class, "md:flex");
+        assert_eq!(rest, ": a Text");
+    }
+
     #[test]
     fn it_should_process_class_with_arbitrary_tailwind_value() {
         let input = ".bg-[#1da1f2]#name Text";
@@ -1,50 +1,73 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, HsmlResult, error::HsmlParseError, span::Span};
 
 use super::process::{process_dev_comment, process_native_comment};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct CommentNode {
     pub text: String,
     pub is_dev: bool,
+    pub span: Span,
 }
 
-pub fn comment_dev_node(input: &str) -> IResult<&str, CommentNode> {
-    let (input, comment) = process_dev_comment(input)?;
+pub fn comment_dev_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, CommentNode> {
+    let (rest, comment) = process_dev_comment(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
 
     Ok((
-        input,
+        rest,
         CommentNode {
             text: comment.to_string(),
             is_dev: true,
+            span: context.span_between(input, rest),
         },
     ))
 }
 
-pub fn comment_native_node(input: &str) -> IResult<&str, CommentNode> {
-    let (input, comment) = process_native_comment(input)?;
+pub fn comment_native_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, CommentNode> {
+    let (rest, comment) = process_native_comment(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
 
     Ok((
-        input,
+        rest,
         CommentNode {
             text: comment.to_string(),
             is_dev: false,
+            span: context.span_between(input, rest),
         },
     ))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::comment::node::{CommentNode, comment_dev_node, comment_native_node};
+    use crate::parser::{
+        HsmlProcessContext,
+        comment::node::{CommentNode, comment_dev_node, comment_native_node},
+        span::Span,
+    };
 
     #[test]
     fn it_should_return_comment_dev_node() {
-        let (input, comment) = comment_dev_node("// This is a dev comment\n").unwrap();
+        let input = "// This is a dev comment\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, comment) = comment_dev_node(input, &context).unwrap();
 
         assert_eq!(
             comment,
             CommentNode {
                 text: String::from(" This is a dev comment"),
                 is_dev: true,
+                span: context.span(0, 24),
             }
         );
 
@@ -53,16 +76,33 @@ mod tests {
 
     #[test]
     fn it_should_return_comment_native_node() {
-        let (input, comment) = comment_native_node("//! This is a native comment\n").unwrap();
+        let input = "//! This is a native comment\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, comment) = comment_native_node(input, &context).unwrap();
 
         assert_eq!(
             comment,
             CommentNode {
                 text: String::from(" This is a native comment"),
                 is_dev: false,
+                span: context.span(0, 28),
             }
         );
 
         assert_eq!(input, "\n");
     }
+
+    #[test]
+    fn it_should_return_comment_node_span_with_line_and_column() {
+        let input = "div\n  // nested\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (_, comment) = comment_dev_node(&input[6..], &context).unwrap();
+
+        let Span { start, end } = comment.span;
+
+        assert_eq!((start.offset, start.line, start.column), (6, 2, 3));
+        assert_eq!((end.offset, end.line, end.column), (15, 2, 12));
+    }
 }
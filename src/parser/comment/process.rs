@@ -8,10 +8,10 @@ pub fn process_dev_comment(input: &str) -> IResult<&str, &str> {
     let (input, _) = tag("//")(input)?;
 
     // check next char is not a `!`
-    if let Some(c) = input.chars().next() {
-        if c == '!' {
-            return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
-        }
+    if let Some(c) = input.chars().next()
+        && c == '!'
+    {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
     }
 
     // read until end of line
@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, HsmlResult, error::HsmlParseError, span::Span};
+
+use super::process::process_doctype;
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DoctypeNode {
+    /// The text after the `doctype` keyword, e.g. `html` or `transitional`, empty if omitted.
+    pub value: String,
+    pub span: Span,
+}
+
+pub fn doctype_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, DoctypeNode> {
+    let (rest, value) = process_doctype(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
+
+    Ok((
+        rest,
+        DoctypeNode {
+            value: value.to_string(),
+            span: context.span_between(input, rest),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{
+        HsmlProcessContext,
+        doctype::node::{DoctypeNode, doctype_node},
+    };
+
+    #[test]
+    fn it_should_return_doctype_node() {
+        let input = "doctype html\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, doctype) = doctype_node(input, &context).unwrap();
+
+        assert_eq!(
+            doctype,
+            DoctypeNode {
+                value: String::from("html"),
+                span: context.span(0, 12),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
+}
@@ -0,0 +1,73 @@
+use nom::{
+    IResult,
+    bytes::complete::{tag, take_till},
+    error::{Error, ErrorKind},
+};
+
+pub fn process_doctype(input: &str) -> IResult<&str, &str> {
+    let (input, _) = tag("doctype")(input)?;
+
+    // the keyword has to stand on its own, e.g. `doctypes` is a tag name
+    if let Some(c) = input.chars().next()
+        && c != ' '
+        && c != '\t'
+        && c != '\r'
+        && c != '\n'
+    {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+    }
+
+    // read until end of line
+    let (input, value) = take_till(|c| c == '\r' || c == '\n')(input)?;
+
+    Ok((input, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::{Error, ErrorKind};
+
+    use crate::parser::doctype::process::process_doctype;
+
+    #[test]
+    fn it_should_process_doctype() {
+        let input = "doctype html\n";
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, "html");
+        assert_eq!(rest, "\n");
+    }
+
+    #[test]
+    fn it_should_process_doctype_without_value() {
+        let input = "doctype";
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, "");
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_process_custom_doctype() {
+        let input = "doctype html PUBLIC \"-//W3C//DTD HTML 4.01//EN\"\r\n";
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, "html PUBLIC \"-//W3C//DTD HTML 4.01//EN\"");
+        assert_eq!(rest, "\r\n");
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_process_tag_starting_with_doctype() {
+        let input = "doctypes text\n";
+
+        assert_eq!(
+            process_doctype(input),
+            Err(nom::Err::Error(Error::new("s text\n", ErrorKind::Tag)))
+        );
+    }
+}
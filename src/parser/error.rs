@@ -0,0 +1,192 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use super::span::Span;
+
+/// Placeholder for source that could not be parsed, only produced when parsing with recovery.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ErrorNode {
+    /// The skipped source, without the indentation of its first line.
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HsmlParseError {
+    InvalidTagName { span: Span },
+    DuplicateId { span: Span },
+    InvalidId { span: Span },
+    MixedIndentation { span: Span },
+    InvalidAttributeKey { span: Span },
+    InvalidAttributeValue { span: Span },
+    UnterminatedAttributeValue { span: Span },
+    UnclosedAttributeList { span: Span },
+    UnclosedArbitraryValue { span: Span },
+    UnexpectedInput { span: Span },
+    UnexpectedEndOfInput { span: Span },
+}
+
+impl HsmlParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            HsmlParseError::InvalidTagName { span }
+            | HsmlParseError::DuplicateId { span }
+            | HsmlParseError::InvalidId { span }
+            | HsmlParseError::MixedIndentation { span }
+            | HsmlParseError::InvalidAttributeKey { span }
+            | HsmlParseError::InvalidAttributeValue { span }
+            | HsmlParseError::UnterminatedAttributeValue { span }
+            | HsmlParseError::UnclosedAttributeList { span }
+            | HsmlParseError::UnclosedArbitraryValue { span }
+            | HsmlParseError::UnexpectedInput { span }
+            | HsmlParseError::UnexpectedEndOfInput { span } => *span,
+        }
+    }
+
+    /// A stable code identifying the kind of error, e.g. for looking it up in the docs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HsmlParseError::InvalidTagName { .. } => "E0001",
+            HsmlParseError::DuplicateId { .. } => "E0002",
+            HsmlParseError::InvalidId { .. } => "E0003",
+            HsmlParseError::MixedIndentation { .. } => "E0004",
+            HsmlParseError::InvalidAttributeKey { .. } => "E0005",
+            HsmlParseError::InvalidAttributeValue { .. } => "E0006",
+            HsmlParseError::UnterminatedAttributeValue { .. } => "E0007",
+            HsmlParseError::UnclosedAttributeList { .. } => "E0008",
+            HsmlParseError::UnclosedArbitraryValue { .. } => "E0009",
+            HsmlParseError::UnexpectedInput { .. } => "E0010",
+            HsmlParseError::UnexpectedEndOfInput { .. } => "E0011",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            HsmlParseError::InvalidTagName { .. } => "Invalid tag name",
+            HsmlParseError::DuplicateId { .. } => "Duplicate id, a tag can only have one id",
+            HsmlParseError::InvalidId { .. } => "Invalid id",
+            HsmlParseError::MixedIndentation { .. } => "Indentation mixes tabs and spaces",
+            HsmlParseError::InvalidAttributeKey { .. } => "Invalid attribute key",
+            HsmlParseError::InvalidAttributeValue { .. } => "Invalid attribute value",
+            HsmlParseError::UnterminatedAttributeValue { .. } => "Unterminated attribute value",
+            HsmlParseError::UnclosedAttributeList { .. } => "Unclosed attribute list",
+            HsmlParseError::UnclosedArbitraryValue { .. } => "Unclosed arbitrary value",
+            HsmlParseError::UnexpectedInput { .. } => "Unexpected input",
+            HsmlParseError::UnexpectedEndOfInput { .. } => "Unexpected end of input",
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            HsmlParseError::InvalidTagName { .. } => Some(
+                "Tag names must start with a letter and can only contain letters, digits, `-` and a `:` after a namespace prefix",
+            ),
+            HsmlParseError::DuplicateId { .. } => Some("Remove one of the `#id` shorthands"),
+            HsmlParseError::InvalidId { .. } => {
+                Some("Ids can only contain letters and digits, use an `id` attribute otherwise")
+            }
+            HsmlParseError::MixedIndentation { .. } => {
+                Some("Indent with either tabs or spaces, but not both")
+            }
+            HsmlParseError::InvalidAttributeKey { .. } => {
+                Some("Attribute keys must start with a letter, `:`, `#`, `@`, `[` or `(`")
+            }
+            HsmlParseError::InvalidAttributeValue { .. } => Some(
+                r#"Unquoted values cannot contain quotes, `=`, `<`, `>` or backticks, wrap the value in quotes, e.g. `key="value"`"#,
+            ),
+            HsmlParseError::UnterminatedAttributeValue { .. } => {
+                Some("Add the closing quote of the attribute value")
+            }
+            HsmlParseError::UnclosedAttributeList { .. } => {
+                Some("Close the attribute list with `)`")
+            }
+            HsmlParseError::UnclosedArbitraryValue { .. } => {
+                Some("Close the arbitrary value with `]`")
+            }
+            HsmlParseError::UnexpectedInput { .. }
+            | HsmlParseError::UnexpectedEndOfInput { .. } => None,
+        }
+    }
+
+    /// Renders the location, the offending source line with the span underlined and the hint.
+    ///
+    /// `line_offset` is added to the line numbers, for sources that are embedded in another file.
+    pub fn snippet(&self, file: &str, source: &str, line_offset: usize) -> String {
+        let span = self.span();
+        let line = source.lines().nth(span.start.line - 1).unwrap_or("");
+        let line_number = (span.start.line + line_offset).to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        // underline at least one char, but never beyond the end of the line
+        let underline_len = if span.end.line == span.start.line {
+            span.len().max(1)
+        } else {
+            line.len().saturating_sub(span.start.column - 1).max(1)
+        };
+
+        let mut snippet = format!(
+            "{gutter}--> {file}:{line_number}:{}\n\
+             {gutter} |\n\
+             {line_number} | {line}\n\
+             {gutter} | {}{}\n",
+            span.start.column,
+            " ".repeat(span.start.column - 1),
+            "^".repeat(underline_len)
+        );
+
+        if let Some(hint) = self.hint() {
+            snippet.push_str(&format!("{gutter} = hint: {hint}\n"));
+        }
+
+        snippet
+    }
+}
+
+impl fmt::Display for HsmlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+
+        write!(
+            f,
+            "error[{}]: {} at {}:{}",
+            self.code(),
+            self.message(),
+            span.start.line,
+            span.start.column
+        )
+    }
+}
+
+impl std::error::Error for HsmlParseError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{error::HsmlParseError, span::LineIndex};
+
+    #[test]
+    fn it_should_render_snippet() {
+        let source = "div\n  p(class=\"a)\n";
+        let err = HsmlParseError::UnterminatedAttributeValue {
+            span: LineIndex::new(source).span(12, 15),
+        };
+
+        assert_eq!(
+            err.snippet("index.hsml", source, 0),
+            " --> index.hsml:2:9\n  |\n2 |   p(class=\"a)\n  |         ^^^\n  = hint: Add the closing quote of the attribute value\n"
+        );
+    }
+
+    #[test]
+    fn it_should_render_snippet_with_line_offset() {
+        let source = "1div\n";
+        let err = HsmlParseError::InvalidTagName {
+            span: LineIndex::new(source).span(0, 1),
+        };
+
+        assert_eq!(
+            err.snippet("src/main.rs", source, 9),
+            "  --> src/main.rs:10:1\n   |\n10 | 1div\n   | ^\n   = hint: Tag names must start with a letter and can only contain letters, digits, `-` and a `:` after a namespace prefix\n"
+        );
+    }
+}
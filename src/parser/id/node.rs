@@ -1,14 +1,42 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, HsmlResult, error::HsmlParseError, span::Span};
 
 use super::process::process_id;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct IdNode {
     pub id: String,
+    pub span: Span,
 }
 
-pub fn id_node(input: &str) -> IResult<&str, IdNode> {
-    let (input, id) = process_id(input)?;
+pub fn id_node<'a>(input: &'a str, context: &HsmlProcessContext) -> HsmlResult<'a, IdNode> {
+    let (rest, id) = process_id(input)
+        .map_err(|err| context.map_error(err, |_, span| HsmlParseError::InvalidId { span }))?;
+
+    // the id has to end where the tag continues, otherwise the rest would be parsed as a new tag
+    let is_id_end = rest.is_empty()
+        || rest.starts_with(|c: char| c.is_whitespace() || c == '.' || c == '#' || c == '(')
+        || rest.starts_with(": ");
+
+    if !is_id_end {
+        // report the whole word that was meant to be the id
+        let id_len = rest
+            .find(|c: char| c.is_whitespace() || c == '.' || c == '#' || c == '(')
+            .unwrap_or(rest.len());
+        let start = context.offset(input);
+        let end = context.offset(&rest[id_len..]);
+
+        return Err(nom::Err::Error(HsmlParseError::InvalidId {
+            span: context.span(start, end),
+        }));
+    }
 
-    Ok((input, IdNode { id: id.to_string() }))
+    Ok((
+        rest,
+        IdNode {
+            id: id.to_string(),
+            span: context.span_between(input, rest),
+        },
+    ))
 }
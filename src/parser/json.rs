@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use super::RootNode;
+
+/// Version of the JSON AST schema produced by [`to_json`].
+///
+/// It is bumped whenever a node type is added, or a node gains, loses or renames a field,
+/// so tools reading the output can reject documents they do not understand.
+pub const AST_SCHEMA_VERSION: u32 = 6;
+
+#[derive(Serialize)]
+struct AstDocument<'a> {
+    version: u32,
+    #[serde(flatten)]
+    root: &'a RootNode,
+}
+
+/// Serializes the AST into the versioned JSON document described in the README.
+pub fn to_json(root: &RootNode, pretty: bool) -> String {
+    let document = AstDocument {
+        version: AST_SCHEMA_VERSION,
+        root,
+    };
+
+    let json = if pretty {
+        serde_json::to_string_pretty(&document)
+    } else {
+        serde_json::to_string(&document)
+    };
+
+    // the AST only consists of strings, bools, options and vectors, so serialization cannot fail
+    json.expect("AST should always be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{json::to_json, parse::parse};
+
+    #[test]
+    fn it_should_serialize_ast_to_json() {
+        let input = "//! note\nbr\n";
+
+        let (_, root_node) = parse(input).unwrap();
+
+        assert_eq!(
+            to_json(&root_node, false),
+            r#"{"version":6,"nodes":[{"type":"comment","text":" note","is_dev":false,"span":{"start":{"offset":0,"line":1,"column":1},"end":{"offset":8,"line":1,"column":9}}},{"type":"tag","tag":"br","id":null,"classes":null,"attributes":null,"text":null,"children":null,"span":{"start":{"offset":9,"line":2,"column":1},"end":{"offset":11,"line":2,"column":3}},"tag_span":{"start":{"offset":9,"line":2,"column":1},"end":{"offset":11,"line":2,"column":3}}}]}"#
+        );
+    }
+
+    #[test]
+    fn it_should_serialize_all_node_parts() {
+        let input = r#"h1#title.text-red(:class="classes" disabled) Hello
+"#;
+
+        let (_, root_node) = parse(input).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&root_node, false)).unwrap();
+        let tag = &json["nodes"][0];
+
+        assert_eq!(tag["type"], "tag");
+        assert_eq!(tag["id"]["id"], "title");
+        assert_eq!(tag["classes"][0]["name"], "text-red");
+        assert_eq!(tag["classes"][0]["span"]["start"]["column"], 9);
+        assert_eq!(tag["attributes"][0]["type"], "attribute");
+        assert_eq!(tag["attributes"][0]["key"], ":class");
+        assert_eq!(tag["attributes"][0]["value"], "classes");
+        assert_eq!(tag["attributes"][0]["quote"], "double");
+        assert_eq!(tag["attributes"][0]["value_span"]["start"]["offset"], 26);
+        assert_eq!(tag["attributes"][1]["value"], serde_json::Value::Null);
+        assert_eq!(tag["attributes"][1]["value_span"], serde_json::Value::Null);
+        assert_eq!(tag["text"]["text"], "Hello");
+        assert_eq!(tag["text"]["span"]["start"]["offset"], 45);
+    }
+
+    #[test]
+    fn it_should_serialize_empty_ast_to_pretty_json() {
+        let (_, root_node) = parse("").unwrap();
+
+        assert_eq!(
+            to_json(&root_node, true),
+            r#"{
+  "version": 6,
+  "nodes": []
+}"#
+        );
+    }
+}
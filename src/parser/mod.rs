@@ -1,40 +1,238 @@
-use nom::{IResult, character::complete::line_ending};
+use nom::{IResult, character::complete::line_ending, error::Error};
+use serde::Serialize;
 
 use self::{
-    attribute::node::AttributeNode, class::node::ClassNode, comment::node::CommentNode,
-    id::node::IdNode, tag::node::TagNode, text::node::TextNode,
+    attribute::node::AttributeNode,
+    class::node::ClassNode,
+    comment::node::CommentNode,
+    doctype::node::DoctypeNode,
+    error::{ErrorNode, HsmlParseError},
+    id::node::IdNode,
+    span::{LineIndex, Span},
+    tag::node::TagNode,
+    text::node::TextNode,
 };
 
 pub mod attribute;
 pub mod class;
 pub mod comment;
+pub mod doctype;
+pub mod error;
 pub mod id;
+pub mod json;
 pub mod parse;
+pub mod span;
 pub mod tag;
 pub mod text;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct RootNode {
     pub nodes: Vec<HsmlNode>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum HsmlNode {
     Root(RootNode),
+    Doctype(DoctypeNode),
     Tag(TagNode),
     Comment(CommentNode),
     Id(IdNode),
     Class(ClassNode),
     Attribute(AttributeNode),
     Text(TextNode),
+    Error(ErrorNode),
 }
 
+impl HsmlNode {
+    /// The `type` of the node as in the AST JSON, e.g. `class`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            HsmlNode::Root(_) => "root",
+            HsmlNode::Doctype(_) => "doctype",
+            HsmlNode::Tag(_) => "tag",
+            HsmlNode::Comment(_) => "comment",
+            HsmlNode::Id(_) => "id",
+            HsmlNode::Class(_) => "class",
+            HsmlNode::Attribute(_) => "attribute",
+            HsmlNode::Text(_) => "text",
+            HsmlNode::Error(_) => "error",
+        }
+    }
+
+    /// `None` for nodes without a location like the root node.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            HsmlNode::Root(_) => None,
+            HsmlNode::Doctype(node) => Some(node.span),
+            HsmlNode::Tag(node) => Some(node.span),
+            HsmlNode::Comment(node) => Some(node.span),
+            HsmlNode::Id(node) => Some(node.span),
+            HsmlNode::Class(node) => Some(node.span),
+            HsmlNode::Attribute(node) => Some(node.span),
+            HsmlNode::Text(node) => Some(node.span),
+            HsmlNode::Error(node) => Some(node.span),
+        }
+    }
+}
+
+pub type HsmlResult<'a, T> = IResult<&'a str, T, HsmlParseError>;
+
 #[derive(Debug, Default)]
 pub struct HsmlProcessContext {
     pub indent_level: usize,
     pub indent_string: Option<String>,
+    pub line_index: LineIndex,
+    /// Whether to record errors and skip the broken source instead of aborting.
+    pub recover: bool,
+    pub errors: Vec<HsmlParseError>,
+}
+
+impl HsmlProcessContext {
+    pub fn new(source: &str) -> Self {
+        Self {
+            line_index: LineIndex::new(source),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the byte offset of the remaining input in the source.
+    ///
+    /// This works because the parsers only ever slice off the start of the input.
+    pub fn offset(&self, input: &str) -> usize {
+        self.line_index.source_len().saturating_sub(input.len())
+    }
+
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        self.line_index.span(start, end)
+    }
+
+    /// Returns the span between two remaining inputs, e.g. before and after a node was parsed.
+    pub fn span_between(&self, input: &str, rest: &str) -> Span {
+        self.span(self.offset(input), self.offset(rest))
+    }
+
+    /// Returns the span of the first char of the remaining input, or an empty span at the end of the input.
+    pub fn char_span(&self, input: &str) -> Span {
+        let start = self.offset(input);
+        let len = input.chars().next().map_or(0, char::len_utf8);
+
+        self.span(start, start + len)
+    }
+
+    /// Converts the error of a process function into a parse error.
+    ///
+    /// `kind` receives the remaining input where the process function failed and the span of its first char.
+    /// Running out of input is always reported as [`HsmlParseError::UnexpectedEndOfInput`].
+    pub fn map_error(
+        &self,
+        err: nom::Err<Error<&str>>,
+        kind: impl FnOnce(&str, Span) -> HsmlParseError,
+    ) -> nom::Err<HsmlParseError> {
+        match err {
+            nom::Err::Incomplete(_) => nom::Err::Error(self.end_of_input_error()),
+            nom::Err::Error(e) => nom::Err::Error(kind(e.input, self.char_span(e.input))),
+            nom::Err::Failure(e) => nom::Err::Failure(kind(e.input, self.char_span(e.input))),
+        }
+    }
+
+    /// Records the error and skips the source line where it happened together with all following
+    /// lines that are indented deeper than `indentation_len`, as they belong to the broken node.
+    ///
+    /// `input` has to start at the first non-whitespace char of the broken line.
+    /// The returned input starts at the line ending in front of the next line that can be parsed again.
+    pub fn recover<'a>(
+        &mut self,
+        input: &'a str,
+        indentation_len: usize,
+        error: HsmlParseError,
+    ) -> (&'a str, ErrorNode) {
+        self.errors.push(error);
+
+        let mut end = input.find('\n').unwrap_or(input.len());
+
+        while end < input.len() {
+            let line_start = end + 1;
+            let line_end = input[line_start..]
+                .find('\n')
+                .map_or(input.len(), |index| line_start + index);
+            let line = input[line_start..line_end].trim_end_matches('\r');
+            let line_indentation_len = line.len() - line.trim_start().len();
+
+            // blank lines do not end the broken node
+            if !line.trim().is_empty() && line_indentation_len <= indentation_len {
+                break;
+            }
+
+            end = line_end;
+        }
+
+        let text = input[..end].trim_end();
+        let start = self.offset(input);
+
+        (
+            &input[end..],
+            ErrorNode {
+                text: text.to_string(),
+                span: self.span(start, start + text.len()),
+            },
+        )
+    }
+
+    pub fn end_of_input_error(&self) -> HsmlParseError {
+        let end = self.line_index.source_len();
+
+        HsmlParseError::UnexpectedEndOfInput {
+            span: self.span(end, end),
+        }
+    }
 }
 
 pub fn process_newline(input: &str) -> IResult<&str, &str> {
     line_ending(input)
 }
+
+/// Resets all spans, so tests can compare the structure of an AST without caring about positions.
+#[cfg(test)]
+pub(crate) fn strip_spans(nodes: &mut [HsmlNode]) {
+    for node in nodes {
+        match node {
+            HsmlNode::Root(root_node) => strip_spans(&mut root_node.nodes),
+            HsmlNode::Tag(tag_node) => {
+                tag_node.span = Span::default();
+                tag_node.tag_span = Span::default();
+
+                if let Some(id_node) = &mut tag_node.id {
+                    id_node.span = Span::default();
+                }
+
+                for class_node in tag_node.classes.iter_mut().flatten() {
+                    class_node.span = Span::default();
+                }
+
+                if let Some(text_node) = &mut tag_node.text {
+                    text_node.span = Span::default();
+                }
+
+                if let Some(attribute_nodes) = &mut tag_node.attributes {
+                    strip_spans(attribute_nodes);
+                }
+
+                if let Some(child_nodes) = &mut tag_node.children {
+                    strip_spans(child_nodes);
+                }
+            }
+            HsmlNode::Doctype(doctype_node) => doctype_node.span = Span::default(),
+            HsmlNode::Comment(comment_node) => comment_node.span = Span::default(),
+            HsmlNode::Id(id_node) => id_node.span = Span::default(),
+            HsmlNode::Class(class_node) => class_node.span = Span::default(),
+            HsmlNode::Attribute(attribute_node) => {
+                attribute_node.span = Span::default();
+                attribute_node.key_span = Span::default();
+                attribute_node.value_span = None;
+            }
+            HsmlNode::Text(text_node) => text_node.span = Span::default(),
+            HsmlNode::Error(error_node) => error_node.span = Span::default(),
+        }
+    }
+}
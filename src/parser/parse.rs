@@ -1,15 +1,49 @@
-use nom::{IResult, bytes::complete::take_till};
+use nom::bytes::complete::take_till;
 
 use super::{
     HsmlNode, HsmlProcessContext, RootNode,
     comment::node::{comment_dev_node, comment_native_node},
+    doctype::node::doctype_node,
+    error::HsmlParseError,
     tag::node::tag_node,
+    text::node::piped_text_node,
 };
 
-pub fn parse(input: &str) -> IResult<&str, RootNode> {
-    let mut nodes: Vec<HsmlNode> = vec![];
+pub fn parse(input: &str) -> Result<(&str, RootNode), HsmlParseError> {
+    let mut context = HsmlProcessContext::new(input);
+
+    parse_nodes(input, &mut context)
+}
+
+/// Parses the input without aborting on the first error.
+///
+/// Broken lines are replaced by [`HsmlNode::Error`] nodes and parsing continues at the next line
+/// with the same or a lower indentation, so the returned AST contains everything that could be parsed.
+/// The returned errors are ordered by their position in the input.
+pub fn parse_recovering(input: &str) -> (RootNode, Vec<HsmlParseError>) {
+    let mut context = HsmlProcessContext {
+        recover: true,
+        ..HsmlProcessContext::new(input)
+    };
+
+    // errors are only returned when not recovering
+    let (_, root_node) = parse_nodes(input, &mut context).unwrap_or_else(|_| {
+        unreachable!("parsing with recovery should never fail");
+    });
 
-    let mut context = HsmlProcessContext::default();
+    // a quoted value can span several lines, so an error can be found after errors of later lines
+    let mut errors = context.errors;
+    errors.sort_by_key(|error| error.span().start.offset);
+    errors.dedup();
+
+    (root_node, errors)
+}
+
+fn parse_nodes<'a>(
+    input: &'a str,
+    context: &mut HsmlProcessContext,
+) -> Result<(&'a str, RootNode), HsmlParseError> {
+    let mut nodes: Vec<HsmlNode> = vec![];
 
     let mut input = input;
 
@@ -25,30 +59,51 @@ pub fn parse(input: &str) -> IResult<&str, RootNode> {
             }
         }
 
-        if let Ok((rest, node)) = comment_native_node(input) {
+        // a doctype can only appear at the root level
+        if let Ok((rest, node)) = doctype_node(input, context) {
+            nodes.push(HsmlNode::Doctype(node));
+            input = rest;
+            continue;
+        }
+
+        if let Ok((rest, node)) = comment_native_node(input, context) {
             nodes.push(HsmlNode::Comment(node));
             input = rest;
             continue;
         }
 
-        if let Ok((rest, node)) = comment_dev_node(input) {
+        if let Ok((rest, node)) = comment_dev_node(input, context) {
             nodes.push(HsmlNode::Comment(node));
             input = rest;
             continue;
         }
 
-        match tag_node(input, &mut context) {
+        if let Ok((rest, node)) = piped_text_node(input, context) {
+            nodes.push(HsmlNode::Text(node));
+            input = rest;
+            continue;
+        }
+
+        let error = match tag_node(input, context) {
             Ok((rest, node)) => {
                 nodes.push(HsmlNode::Tag(node));
                 input = rest;
                 continue;
             }
-            Err(e) => {
-                return Err(e);
-            }
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => e,
+            Err(nom::Err::Incomplete(_)) => context.end_of_input_error(),
+        };
+
+        if !context.recover {
+            return Err(error);
         }
 
-        // TODO @Shinigami92 2023-05-18: Add support for doctype node
+        // a failed tag node does not restore the indentation level of its children
+        context.indent_level = 0;
+
+        let (rest, node) = context.recover(input, 0, error);
+        nodes.push(HsmlNode::Error(node));
+        input = rest;
     }
 
     Ok((input, RootNode { nodes }))
@@ -56,11 +111,18 @@ pub fn parse(input: &str) -> IResult<&str, RootNode> {
 
 #[cfg(test)]
 mod tests {
-    use nom::error::{Error, ErrorKind};
-
     use crate::parser::{
-        HsmlNode, RootNode, attribute::node::AttributeNode, class::node::ClassNode,
-        comment::node::CommentNode, parse::parse, tag::node::TagNode, text::node::TextNode,
+        HsmlNode, RootNode,
+        attribute::node::AttributeNode,
+        class::node::ClassNode,
+        comment::node::CommentNode,
+        doctype::node::DoctypeNode,
+        error::{ErrorNode, HsmlParseError},
+        parse::{parse, parse_recovering},
+        span::LineIndex,
+        strip_spans,
+        tag::node::TagNode,
+        text::node::TextNode,
     };
 
     #[test]
@@ -74,7 +136,8 @@ mod tests {
   .card__body {{ fullName }}
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let (input, mut root_node) = parse(input).unwrap();
+        strip_spans(&mut root_node.nodes);
 
         assert_eq!(
             root_node,
@@ -84,19 +147,23 @@ mod tests {
                         tag: String::from("h1"),
                         id: None,
                         classes: Some(vec![ClassNode {
-                            name: String::from("text-red")
+                            name: String::from("text-red"),
+                            ..Default::default()
                         }]),
                         attributes: None,
                         text: Some(TextNode {
                             text: String::from("Vite CJS Faker Demo"),
+                            ..Default::default()
                         }),
                         children: None,
+                        ..Default::default()
                     }),
                     HsmlNode::Tag(TagNode {
                         tag: String::from("div"),
                         id: None,
                         classes: Some(vec![ClassNode {
                             name: String::from("card"),
+                            ..Default::default()
                         }]),
                         attributes: None,
                         text: None,
@@ -106,6 +173,7 @@ mod tests {
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__image"),
+                                    ..Default::default()
                                 }]),
                                 attributes: None,
                                 text: None,
@@ -117,23 +185,28 @@ mod tests {
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":src"),
                                             value: Some(String::from("natureImageUrl")),
+                                            ..Default::default()
                                         }),
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":alt"),
                                             value: Some(String::from(
                                                 "'Background image for ' + fullName"
                                             )),
+                                            ..Default::default()
                                         }),
                                     ]),
                                     text: None,
                                     children: None,
+                                    ..Default::default()
                                 })]),
+                                ..Default::default()
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("div"),
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__profile"),
+                                    ..Default::default()
                                 }]),
                                 attributes: None,
                                 text: None,
@@ -145,31 +218,39 @@ mod tests {
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":src"),
                                             value: Some(String::from("avatarUrl")),
+                                            ..Default::default()
                                         }),
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":alt"),
                                             value: Some(String::from(
                                                 "'Avatar image of ' + fullName"
                                             )),
+                                            ..Default::default()
                                         }),
                                     ]),
                                     text: None,
                                     children: None,
+                                    ..Default::default()
                                 })]),
+                                ..Default::default()
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("div"),
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__body"),
+                                    ..Default::default()
                                 }]),
                                 attributes: None,
                                 text: Some(TextNode {
                                     text: String::from("{{ fullName }}"),
+                                    ..Default::default()
                                 }),
                                 children: None,
+                                ..Default::default()
                             })
                         ]),
+                        ..Default::default()
                     }),
                 ],
             }
@@ -196,7 +277,8 @@ div
     )
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let (input, mut root_node) = parse(input).unwrap();
+        strip_spans(&mut root_node.nodes);
 
         assert_eq!(
             root_node,
@@ -205,10 +287,12 @@ div
                     HsmlNode::Comment(CommentNode {
                         text: String::from(" this is a root dev comment"),
                         is_dev: true,
+                        ..Default::default()
                     }),
                     HsmlNode::Comment(CommentNode {
                         text: String::from(" this is a root native comment (will get rendered)"),
                         is_dev: false,
+                        ..Default::default()
                     }),
                     HsmlNode::Tag(TagNode {
                         tag: String::from("div"),
@@ -220,6 +304,7 @@ div
                             HsmlNode::Comment(CommentNode {
                                 text: String::from(" this is a child comment"),
                                 is_dev: true,
+                                ..Default::default()
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("p"),
@@ -227,13 +312,16 @@ div
                                 classes: None,
                                 attributes: None,
                                 text: Some(TextNode {
-                                    text: String::from("another tag")
+                                    text: String::from("another tag"),
+                                    ..Default::default()
                                 }),
                                 children: None,
+                                ..Default::default()
                             }),
                             HsmlNode::Comment(CommentNode {
                                 text: String::from(" this is a child comment that gets rendered"),
                                 is_dev: false,
+                                ..Default::default()
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("img"),
@@ -243,32 +331,40 @@ div
                                     HsmlNode::Comment(CommentNode {
                                         text: String::from(" supports attribute inline comments"),
                                         is_dev: true,
+                                        ..Default::default()
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("src"),
                                         value: Some(String::from("/fancy-avatar.jpg")),
+                                        ..Default::default()
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("alt"),
                                         value: Some(String::from("Fancy Avatar")),
+                                        ..Default::default()
                                     }),
                                     HsmlNode::Comment(CommentNode {
                                         text: String::from(" the size of the image"),
                                         is_dev: true,
+                                        ..Default::default()
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("width"),
                                         value: Some(String::from("384")),
+                                        ..Default::default()
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("height"),
                                         value: Some(String::from("512")),
+                                        ..Default::default()
                                     }),
                                 ]),
                                 text: None,
                                 children: None,
+                                ..Default::default()
                             }),
-                        ])
+                        ]),
+                        ..Default::default()
                     })
                 ]
             }
@@ -287,7 +383,8 @@ div
 )
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let (input, mut root_node) = parse(input).unwrap();
+        strip_spans(&mut root_node.nodes);
 
         assert_eq!(
             root_node,
@@ -298,31 +395,38 @@ div
                     classes: Some(vec![
                         ClassNode {
                             name: String::from("rounded-full"),
+                            ..Default::default()
                         },
                         ClassNode {
                             name: String::from("mx-auto"),
+                            ..Default::default()
                         },
                     ]),
                     attributes: Some(vec![
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("src"),
                             value: Some(String::from("/fancy-avatar.jpg")),
+                            ..Default::default()
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("alt"),
                             value: Some(String::from("A fancy avatar")),
+                            ..Default::default()
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("width"),
                             value: Some(String::from("384")),
+                            ..Default::default()
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("height"),
                             value: Some(String::from("512")),
+                            ..Default::default()
                         }),
                     ]),
                     text: None,
                     children: None,
+                    ..Default::default()
                 })],
             }
         );
@@ -330,17 +434,369 @@ div
         assert_eq!(input, "");
     }
 
+    #[test]
+    fn it_should_parse_with_spans() {
+        let input = "div\n  // comment\n  span#main.red(title=\"x\") text\n";
+
+        let (_, root_node) = parse(input).unwrap();
+
+        let HsmlNode::Tag(div) = &root_node.nodes[0] else {
+            panic!("expected tag node");
+        };
+
+        assert_eq!((div.span.start.offset, div.span.end.offset), (0, 48));
+        assert_eq!((div.tag_span.start.offset, div.tag_span.end.offset), (0, 3));
+
+        let children = div.children.as_ref().unwrap();
+
+        let HsmlNode::Comment(comment) = &children[0] else {
+            panic!("expected comment node");
+        };
+
+        assert_eq!((comment.span.start.line, comment.span.start.column), (2, 3));
+        assert_eq!((comment.span.end.line, comment.span.end.column), (2, 13));
+
+        let HsmlNode::Tag(span) = &children[1] else {
+            panic!("expected tag node");
+        };
+
+        assert_eq!((span.span.start.line, span.span.start.column), (3, 3));
+        assert_eq!(
+            (span.tag_span.start.offset, span.tag_span.end.offset),
+            (19, 23)
+        );
+
+        let id = span.id.as_ref().unwrap();
+        assert_eq!((id.span.start.offset, id.span.end.offset), (23, 28));
+
+        let class = &span.classes.as_ref().unwrap()[0];
+        assert_eq!((class.span.start.offset, class.span.end.offset), (28, 32));
+
+        let HsmlNode::Attribute(attribute) = &span.attributes.as_ref().unwrap()[0] else {
+            panic!("expected attribute node");
+        };
+        assert_eq!(
+            (
+                attribute.key_span.start.offset,
+                attribute.key_span.end.offset
+            ),
+            (33, 38)
+        );
+
+        let value_span = attribute.value_span.unwrap();
+        assert_eq!((value_span.start.offset, value_span.end.offset), (40, 41));
+
+        let text = span.text.as_ref().unwrap();
+        assert_eq!((text.span.start.column, text.span.end.column), (28, 32));
+    }
+
+    #[test]
+    fn it_should_parse_doctype() {
+        let input = "doctype html\nhtml\n";
+
+        let (rest, root_node) = parse(input).unwrap();
+
+        assert_eq!(
+            root_node.nodes[0],
+            HsmlNode::Doctype(DoctypeNode {
+                value: String::from("html"),
+                span: LineIndex::new(input).span(0, 12),
+            })
+        );
+        assert!(matches!(&root_node.nodes[1], HsmlNode::Tag(TagNode { tag, .. }) if tag == "html"));
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_parse_piped_text() {
+        let input = "| root\np\n  | Click \n  a here\n  |  to continue\n";
+
+        let (rest, mut root_node) = parse(input).unwrap();
+        strip_spans(&mut root_node.nodes);
+
+        let text = |text: &str| {
+            HsmlNode::Text(TextNode {
+                text: String::from(text),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            root_node,
+            RootNode {
+                nodes: vec![
+                    text("root"),
+                    HsmlNode::Tag(TagNode {
+                        tag: String::from("p"),
+                        children: Some(vec![
+                            text("Click "),
+                            HsmlNode::Tag(TagNode {
+                                tag: String::from("a"),
+                                text: Some(TextNode {
+                                    text: String::from("here"),
+                                    ..Default::default()
+                                }),
+                                ..Default::default()
+                            }),
+                            text(" to continue"),
+                        ]),
+                        ..Default::default()
+                    }),
+                ]
+            }
+        );
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_parse_recovering_without_errors() {
+        let input = "div\n  span text\n";
+
+        let (root_node, errors) = parse_recovering(input);
+
+        assert_eq!(root_node, parse(input).unwrap().1);
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn it_should_parse_recovering_and_collect_all_errors() {
+        let input = r#"div#a#b
+  span skipped
+p ok
+ul
+  li first
+  42li broken
+
+    span skipped
+  li(href="/home third
+  li fourth
+footer done
+"#;
+
+        let (mut root_node, errors) = parse_recovering(input);
+
+        let line_index = LineIndex::new(input);
+        assert_eq!(
+            errors,
+            vec![
+                HsmlParseError::DuplicateId {
+                    span: line_index.span(5, 7)
+                },
+                HsmlParseError::InvalidTagName {
+                    span: line_index.span(44, 48)
+                },
+                HsmlParseError::UnterminatedAttributeValue {
+                    span: line_index.span(84, 85)
+                },
+            ]
+        );
+
+        let HsmlNode::Error(error_node) = &root_node.nodes[0] else {
+            panic!("expected an error node");
+        };
+        assert_eq!(error_node.span, line_index.span(0, 22));
+
+        strip_spans(&mut root_node.nodes);
+
+        let text_tag = |tag: &str, text: &str| {
+            HsmlNode::Tag(TagNode {
+                tag: String::from(tag),
+                text: Some(TextNode {
+                    text: String::from(text),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            root_node,
+            RootNode {
+                nodes: vec![
+                    HsmlNode::Error(ErrorNode {
+                        text: String::from("div#a#b\n  span skipped"),
+                        ..Default::default()
+                    }),
+                    text_tag("p", "ok"),
+                    HsmlNode::Tag(TagNode {
+                        tag: String::from("ul"),
+                        children: Some(vec![
+                            text_tag("li", "first"),
+                            HsmlNode::Error(ErrorNode {
+                                text: String::from("42li broken\n\n    span skipped"),
+                                ..Default::default()
+                            }),
+                            HsmlNode::Error(ErrorNode {
+                                text: String::from(r#"li(href="/home third"#),
+                                ..Default::default()
+                            }),
+                            text_tag("li", "fourth"),
+                        ]),
+                        ..Default::default()
+                    }),
+                    text_tag("footer", "done"),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_return_errors_ordered_by_position() {
+        let input = r#"div
+  p ok
+ul
+  li(title="broken
+  li second
+  42li
+section
+  p ok
+  a(href="/home") Home
+"#;
+
+        let (_, errors) = parse_recovering(input);
+
+        let line_index = LineIndex::new(input);
+        assert_eq!(
+            errors,
+            vec![
+                HsmlParseError::InvalidTagName {
+                    span: line_index.span(47, 51)
+                },
+                HsmlParseError::InvalidAttributeKey {
+                    span: line_index.span(77, 78)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_parse_recovering_from_mixed_indentation() {
+        let input = "div\n \tspan skipped\n  p ok\n";
+
+        let (mut root_node, errors) = parse_recovering(input);
+
+        assert_eq!(
+            errors,
+            vec![HsmlParseError::MixedIndentation {
+                span: LineIndex::new(input).span(4, 6)
+            }]
+        );
+
+        strip_spans(&mut root_node.nodes);
+
+        let HsmlNode::Tag(tag_node) = &root_node.nodes[0] else {
+            panic!("expected a tag node");
+        };
+        let child_nodes = tag_node.children.as_ref().unwrap();
+
+        assert_eq!(
+            child_nodes[0],
+            HsmlNode::Error(ErrorNode {
+                text: String::from("span skipped"),
+                ..Default::default()
+            })
+        );
+        assert!(matches!(&child_nodes[1], HsmlNode::Tag(TagNode { tag, .. }) if tag == "p"));
+    }
+
     // Negative tests
 
     #[test]
     fn it_should_not_parse_tag_with_multiple_ids() {
         let input = r#"div#id1#id2"#;
 
+        let line_index = LineIndex::new(input);
+
+        assert_eq!(
+            Err(HsmlParseError::DuplicateId {
+                span: line_index.span(7, 11)
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_invalid_tag_name() {
+        let input = "div\n  42span.red text\n";
+
+        let err = parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            HsmlParseError::InvalidTagName {
+                span: LineIndex::new(input).span(6, 16)
+            }
+        );
+        assert_eq!(err.code(), "E0001");
+        assert_eq!(err.to_string(), "error[E0001]: Invalid tag name at 2:3");
+    }
+
+    #[test]
+    fn it_should_not_parse_invalid_id() {
+        let input = "div\n  p#a-b.red text\n";
+
+        let err = parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            HsmlParseError::InvalidId {
+                span: LineIndex::new(input).span(7, 11)
+            }
+        );
+        assert_eq!(err.code(), "E0003");
+        assert_eq!(err.to_string(), "error[E0003]: Invalid id at 2:4");
+    }
+
+    #[test]
+    fn it_should_not_parse_mixed_indentation() {
+        let input = "div\n \tp text\n";
+
+        assert_eq!(
+            Err(HsmlParseError::MixedIndentation {
+                span: LineIndex::new(input).span(4, 6)
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unterminated_attribute_value() {
+        let input = "a(href=\"/home)\n";
+
+        let err = parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            HsmlParseError::UnterminatedAttributeValue {
+                span: LineIndex::new(input).span(7, 8)
+            }
+        );
+        assert_eq!(
+            err.hint(),
+            Some("Add the closing quote of the attribute value")
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unclosed_attribute_list() {
+        let input = "div\n  a(href=\"/home\"";
+
+        assert_eq!(
+            Err(HsmlParseError::UnclosedAttributeList {
+                span: LineIndex::new(input).span(7, 8)
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unclosed_arbitrary_value() {
+        let input = "div.bg-[#1da1f2 text\n";
+
         assert_eq!(
-            Err(nom::Err::Failure(Error {
-                input: "#id2",
-                code: ErrorKind::Tag
-            })),
+            Err(HsmlParseError::UnclosedArbitraryValue {
+                span: LineIndex::new(input).span(7, 8)
+            }),
             parse(input)
         );
     }
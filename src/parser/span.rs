@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+/// A location in the source.
+///
+/// `line` and `column` are 1-based, the column is counted in bytes from the start of the line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The byte range `start..end` of a node in the source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Converts between byte offsets and line/column positions of a source.
+#[derive(Debug, Default, Clone)]
+pub struct LineIndex {
+    /// Byte offset of the first char of every line.
+    line_starts: Vec<usize>,
+    source_len: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+
+        line_starts.extend(
+            source
+                .bytes()
+                .enumerate()
+                .filter(|&(_, b)| b == b'\n')
+                .map(|(index, _)| index + 1),
+        );
+
+        Self {
+            line_starts,
+            source_len: source.len(),
+        }
+    }
+
+    pub fn source_len(&self) -> usize {
+        self.source_len
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len().max(1)
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.source_len);
+
+        let line_index = self
+            .line_starts
+            .partition_point(|&line_start| line_start <= offset)
+            .saturating_sub(1);
+        let line_start = self.line_starts.get(line_index).copied().unwrap_or(0);
+
+        Position {
+            offset,
+            line: line_index + 1,
+            column: offset - line_start + 1,
+        }
+    }
+
+    /// Returns the byte offset of a 1-based line and column, if it lies within the source.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        let line_start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let line_end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.source_len + 1);
+
+        let offset = line_start + column.checked_sub(1)?;
+
+        (offset < line_end).then_some(offset)
+    }
+
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            start: self.position(start),
+            end: self.position(end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::span::{LineIndex, Position};
+
+    #[test]
+    fn it_should_convert_offset_to_position() {
+        let line_index = LineIndex::new("div\n  p text\n\nspan");
+
+        assert_eq!(
+            line_index.position(0),
+            Position {
+                offset: 0,
+                line: 1,
+                column: 1
+            }
+        );
+        assert_eq!(
+            line_index.position(6),
+            Position {
+                offset: 6,
+                line: 2,
+                column: 3
+            }
+        );
+        assert_eq!(
+            line_index.position(13),
+            Position {
+                offset: 13,
+                line: 3,
+                column: 1
+            }
+        );
+        assert_eq!(
+            line_index.position(18),
+            Position {
+                offset: 18,
+                line: 4,
+                column: 5
+            }
+        );
+        assert_eq!(line_index.line_count(), 4);
+    }
+
+    #[test]
+    fn it_should_convert_position_to_offset() {
+        let line_index = LineIndex::new("div\n  p text\n\nspan");
+
+        assert_eq!(line_index.offset(1, 1), Some(0));
+        assert_eq!(line_index.offset(2, 3), Some(6));
+        assert_eq!(line_index.offset(3, 1), Some(13));
+        assert_eq!(line_index.offset(4, 5), Some(18));
+
+        // out of range
+        assert_eq!(line_index.offset(0, 1), None);
+        assert_eq!(line_index.offset(1, 0), None);
+        assert_eq!(line_index.offset(1, 5), None);
+        assert_eq!(line_index.offset(5, 1), None);
+    }
+}
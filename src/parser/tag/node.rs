@@ -1,19 +1,24 @@
 use nom::{
-    IResult,
     bytes::complete::{take_till, take_till1},
-    error::{Error, ErrorKind},
+    error::Error,
 };
+use serde::Serialize;
 
 use crate::parser::{
-    HsmlNode, HsmlProcessContext, attribute,
+    HsmlNode, HsmlProcessContext, HsmlResult, attribute,
     class::node::{ClassNode, class_node},
     comment::node::{comment_dev_node, comment_native_node},
+    error::HsmlParseError,
     id::{self, node::IdNode},
+    span::Span,
     tag::process::process_tag,
-    text::{self, node::TextNode},
+    text::{
+        self,
+        node::{TextNode, piped_text_node},
+    },
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq, Serialize)]
 pub struct TagNode {
     pub tag: String,
     pub id: Option<IdNode>,
@@ -21,18 +26,35 @@ pub struct TagNode {
     pub attributes: Option<Vec<HsmlNode>>,
     pub text: Option<TextNode>,
     pub children: Option<Vec<HsmlNode>>,
+    /// Span of the whole node including its children.
+    pub span: Span,
+    /// Span of the tag name, empty if the tag name is an implicit `div`.
+    pub tag_span: Span,
 }
 
-pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult<&'a str, TagNode> {
+pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> HsmlResult<'a, TagNode> {
+    let start = context.offset(input);
+
     // tag node starts with a tag name or a dot/hash
     // if it starts with a dot/hash, the tag name is div
 
     let (mut input, tag_name) = if input.starts_with('.') || input.starts_with('#') {
         (input, "div")
     } else {
-        process_tag(input)?
+        process_tag(input).map_err(|_| {
+            // report the whole word that was meant to be the tag name
+            let tag_name_len = input
+                .find(|c: char| c.is_whitespace() || c == '(')
+                .unwrap_or(input.len());
+
+            nom::Err::Error(HsmlParseError::InvalidTagName {
+                span: context.span(start, start + tag_name_len),
+            })
+        })?
     };
 
+    let tag_span = context.span(start, context.offset(input));
+
     // if the next char is a dot, we have a id node
     // if the next char is a dot, we have a class node
     // collect id and class nodes until we hit a whitespace, newline, start of attributes or single dot without trailing alphabetical char
@@ -50,14 +72,15 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
         if first_char == Some("#") {
             // we hit an id node
 
+            let (rest, node) = id::node::id_node(input, context)?;
+
             // if there was already an id node, throw an error
             if id_node.is_some() {
-                // TODO @Shinigami92 2023-05-25: This error could be more specific
-                // Duplicate attribute "id" is not allowed.
-                return Err(nom::Err::Failure(Error::new(input, ErrorKind::Tag)));
+                return Err(nom::Err::Failure(HsmlParseError::DuplicateId {
+                    span: node.span,
+                }));
             }
 
-            let (rest, node) = id::node::id_node(input)?;
             id_node = Some(node);
             input = rest;
 
@@ -75,7 +98,7 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             }
 
             // we hit a class node
-            let (rest, node) = class_node(input)?;
+            let (rest, node) = class_node(input, context)?;
             class_nodes.push(node);
             input = rest;
 
@@ -92,10 +115,21 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             continue;
         }
 
+        if first_two_chars == Some(": ") {
+            // we hit a block expansion, the rest of the line is the only child tag node
+            // it keeps the indentation level, so indented lines below become its children
+
+            let (rest, node) = tag_node(input[1..].trim_start_matches(' '), context)?;
+            child_nodes.push(HsmlNode::Tag(node));
+            input = rest;
+
+            continue;
+        }
+
         if first_char == Some(" ") {
             // we hit a whitespace and there should be text
 
-            let (rest, node) = text::node::text_node(input)?;
+            let (rest, node) = text::node::text_node(input, context)?;
             text_node = Some(node);
             input = rest;
 
@@ -110,21 +144,40 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             // we hit a newline and the tag ended but could have child tag nodes
 
             // check indentation
-            let (rest, _) = take_till1(|c| c != '\r' && c != '\n')(input)?;
+            let (rest, _) = take_till1::<_, _, Error<&str>>(|c| c != '\r' && c != '\n')(input)
+                .map_err(|err| {
+                    context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+                })?;
 
             // check if the next char is a tab or whitespace
             // if yes, check for indentation level
             // if no, we have no child tag nodes and can break the loop
 
-            let (remaining, indentation) = take_till(|c: char| !c.is_whitespace())(rest)?;
+            let (remaining, indentation) =
+                take_till::<_, _, Error<&str>>(|c: char| !c.is_whitespace())(rest).map_err(
+                    |err| {
+                        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+                    },
+                )?;
 
             if !indentation.is_empty() {
                 // check that the indentation is consistent and does not include tabs and spaces at the same time
                 // if it does, throw an error
 
                 if indentation.contains('\t') && indentation.contains(' ') {
-                    // TODO @Shinigami92 2023-05-18: This error could be more specific
-                    return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+                    let error = HsmlParseError::MixedIndentation {
+                        span: context.span_between(rest, remaining),
+                    };
+
+                    if !context.recover {
+                        return Err(nom::Err::Error(error));
+                    }
+
+                    let (rest, node) = context.recover(remaining, indentation.len(), error);
+                    child_nodes.push(HsmlNode::Error(node));
+                    input = rest;
+
+                    continue;
                 }
 
                 // if we never hit an indentation yet, set it
@@ -151,21 +204,35 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
                 // we are at the correct indentation level, so we can continue parsing the child tag nodes
 
                 // there could be a comment (dev or native) node
-                if let Ok((rest, node)) = comment_native_node(remaining) {
+                if let Ok((rest, node)) = comment_native_node(remaining, context) {
                     child_nodes.push(HsmlNode::Comment(node));
                     input = rest;
-                } else if let Ok((rest, node)) = comment_dev_node(remaining) {
+                } else if let Ok((rest, node)) = comment_dev_node(remaining, context) {
                     child_nodes.push(HsmlNode::Comment(node));
                     input = rest;
                 }
+                // or a piped text node
+                else if let Ok((rest, node)) = piped_text_node(remaining, context) {
+                    child_nodes.push(HsmlNode::Text(node));
+                    input = rest;
+                }
                 // or we have now a child tag node
                 else {
                     // now we have a child tag node
-                    if let Ok((rest, node)) = tag_node(remaining, context) {
-                        child_nodes.push(HsmlNode::Tag(node));
-                        input = rest;
-                    } else {
-                        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+                    match tag_node(remaining, context) {
+                        Ok((rest, node)) => {
+                            child_nodes.push(HsmlNode::Tag(node));
+                            input = rest;
+                        }
+                        Err(nom::Err::Error(error) | nom::Err::Failure(error))
+                            if context.recover =>
+                        {
+                            // skip the broken child and its children, but keep its siblings
+                            let (rest, node) = context.recover(remaining, indentation.len(), error);
+                            child_nodes.push(HsmlNode::Error(node));
+                            input = rest;
+                        }
+                        Err(err) => return Err(err),
                     }
                 }
 
@@ -191,6 +258,8 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             attributes: attribute_nodes,
             text: text_node,
             children: (!child_nodes.is_empty()).then_some(child_nodes),
+            span: context.span(start, context.offset(input)),
+            tag_span,
         },
     ))
 }
@@ -198,7 +267,7 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
 #[cfg(test)]
 mod tests {
     use crate::parser::{
-        HsmlProcessContext,
+        HsmlNode, HsmlProcessContext,
         class::node::ClassNode,
         tag::node::{TagNode, tag_node},
         text::node::TextNode,
@@ -206,20 +275,19 @@ mod tests {
 
     #[test]
     fn it_should_return_tag_node_with_piped_text() {
+        let input = r#"p.text-lg.font-medium.
+        "Tailwind CSS is the only framework that I've seen scale
+        on large teams. It's easy to customize, adapts to any design,
+        and the build size is tiny."
+    figcaption.font-medium"#;
+
         let context = &mut HsmlProcessContext {
             indent_level: 3,
             indent_string: Some(String::from("  ")),
+            ..HsmlProcessContext::new(input)
         };
 
-        let (input, tag) = tag_node(
-            r#"p.text-lg.font-medium.
-        "Tailwind CSS is the only framework that I've seen scale
-        on large teams. It's easy to customize, adapts to any design,
-        and the build size is tiny."
-    figcaption.font-medium"#,
-            context,
-        )
-        .unwrap();
+        let (input, tag) = tag_node(input, context).unwrap();
 
         assert_eq!(
             tag,
@@ -229,9 +297,11 @@ mod tests {
                 classes: Some(vec![
                     ClassNode {
                         name: String::from("text-lg"),
+                        span: context.span(1, 9),
                     },
                     ClassNode {
                         name: String::from("font-medium"),
+                        span: context.span(9, 21),
                     },
                 ]),
                 attributes: None,
@@ -241,11 +311,44 @@ mod tests {
 on large teams. It's easy to customize, adapts to any design,
 and the build size is tiny.""#
                     ),
+                    span: context.span(23, 194),
                 }),
                 children: None,
+                span: context.span(0, 194),
+                tag_span: context.span(0, 1),
             }
         );
 
         assert_eq!(input, "\n    figcaption.font-medium");
     }
+
+    #[test]
+    fn it_should_return_tag_node_with_block_expansion() {
+        let input = "li.md:flex: a#home: span\n  b bold\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (input, tag) = tag_node(input, context).unwrap();
+
+        assert_eq!(input, "\n");
+        assert_eq!(tag.tag, "li");
+        assert_eq!(tag.classes.unwrap()[0].name, "md:flex");
+        assert_eq!(tag.span, context.span(0, 33));
+
+        let Some([HsmlNode::Tag(a_tag)]) = tag.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+        assert_eq!(a_tag.tag, "a");
+        assert_eq!(a_tag.tag_span, context.span(12, 13));
+
+        let Some([HsmlNode::Tag(span_tag)]) = a_tag.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+        assert_eq!(span_tag.tag, "span");
+
+        // indented lines below belong to the innermost tag node
+        let Some([HsmlNode::Tag(b_tag)]) = span_tag.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+        assert_eq!(b_tag.tag, "b");
+    }
 }
@@ -1,4 +1,7 @@
-use nom::{IResult, Needed, bytes::complete::take_till1};
+use nom::{
+    IResult, Needed,
+    error::{Error, ErrorKind},
+};
 
 fn starts_with_ascii_alphabetic(s: &str) -> bool {
     if let Some(c) = s.chars().next() {
@@ -9,7 +12,26 @@ fn starts_with_ascii_alphabetic(s: &str) -> bool {
 }
 
 pub fn process_tag(input: &str) -> IResult<&str, &str> {
-    let (input, tag_name) = take_till1(|c: char| c != '-' && !c.is_ascii_alphanumeric())(input)?;
+    let mut tag_name_len = 0;
+
+    for (index, c) in input.char_indices() {
+        // a colon is only part of the tag name if it separates a namespace prefix, e.g. `svg:rect`
+        // otherwise it is a block expansion like `li: a`
+        let is_namespace_separator =
+            c == ':' && index > 0 && starts_with_ascii_alphabetic(&input[index + 1..]);
+
+        if c != '-' && !c.is_ascii_alphanumeric() && !is_namespace_separator {
+            break;
+        }
+
+        tag_name_len = index + c.len_utf8();
+    }
+
+    if tag_name_len == 0 {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::TakeTill1)));
+    }
+
+    let (tag_name, input) = input.split_at(tag_name_len);
 
     if starts_with_ascii_alphabetic(tag_name) {
         Ok((input, tag_name))
@@ -107,6 +129,26 @@ mod tests {
         assert_eq!(rest, ".input");
     }
 
+    #[test]
+    fn it_should_process_tag_with_namespace_prefix() {
+        let input = "svg:rect(x=\"0\")";
+
+        let (rest, tag) = process_tag(input).unwrap();
+
+        assert_eq!(tag, "svg:rect");
+        assert_eq!(rest, "(x=\"0\")");
+    }
+
+    #[test]
+    fn it_should_process_tag_with_block_expansion() {
+        let input = "li: a";
+
+        let (rest, tag) = process_tag(input).unwrap();
+
+        assert_eq!(tag, "li");
+        assert_eq!(rest, ": a");
+    }
+
     // Negative tests
 
     #[test]
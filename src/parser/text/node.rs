@@ -1,24 +1,31 @@
-use nom::IResult;
+use serde::Serialize;
 
-use crate::parser::HsmlProcessContext;
+use crate::parser::{HsmlProcessContext, HsmlResult, error::HsmlParseError, span::Span};
 
-use super::process::{process_text, process_text_block};
+use super::process::{process_piped_text, process_text, process_text_block};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct TextNode {
     pub text: String,
+    pub span: Span,
 }
 
 pub fn text_block_node<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, TextNode> {
-    let (input, text) = process_text_block(input, context)?;
+) -> HsmlResult<'a, TextNode> {
+    let (input, text) = process_text_block(input, context).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
+
+    // the text block is directly followed by the remaining input
+    let end = context.offset(input);
+    let span = context.span(end - text.len(), end);
 
     let indent_string = context
         .indent_string
-        .as_ref()
-        .unwrap()
+        .as_deref()
+        .unwrap_or("  ")
         .repeat(context.indent_level + 1);
 
     let newline_indent_replacement: &str = &format!("\n{}", &indent_string);
@@ -27,16 +34,39 @@ pub fn text_block_node<'a>(
         .trim_start_matches(&indent_string)
         .replace(newline_indent_replacement, "\n");
 
-    Ok((input, TextNode { text }))
+    Ok((input, TextNode { text, span }))
 }
 
-pub fn text_node(input: &str) -> IResult<&str, TextNode> {
-    let (input, text) = process_text(input)?;
+pub fn text_node<'a>(input: &'a str, context: &HsmlProcessContext) -> HsmlResult<'a, TextNode> {
+    let (input, text) = process_text(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
+
+    let end = context.offset(input);
 
     Ok((
         input,
         TextNode {
             text: text.to_string(),
+            span: context.span(end - text.len(), end),
+        },
+    ))
+}
+
+/// Parses a `| text` line, the span includes the pipe.
+pub fn piped_text_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_piped_text(input).map_err(|err| {
+        context.map_error(err, |_, span| HsmlParseError::UnexpectedInput { span })
+    })?;
+
+    Ok((
+        rest,
+        TextNode {
+            text: text.to_string(),
+            span: context.span_between(input, rest),
         },
     ))
 }
@@ -45,25 +75,24 @@ pub fn text_node(input: &str) -> IResult<&str, TextNode> {
 mod tests {
     use crate::parser::{
         HsmlProcessContext,
-        text::node::{TextNode, text_block_node},
+        text::node::{TextNode, piped_text_node, text_block_node, text_node},
     };
 
     #[test]
     fn it_should_return_text_block_node() {
+        let input = r#".
+        "Tailwind CSS is the only framework that I've seen scale
+        on large teams. It's easy to customize, adapts to any design,
+        and the build size is tiny."
+    figcaption.font-medium"#;
+
         let context = &mut HsmlProcessContext {
             indent_string: Some(String::from("  ")),
             indent_level: 3,
+            ..HsmlProcessContext::new(input)
         };
 
-        let (input, text_block) = text_block_node(
-            r#".
-        "Tailwind CSS is the only framework that I've seen scale
-        on large teams. It's easy to customize, adapts to any design,
-        and the build size is tiny."
-    figcaption.font-medium"#,
-            context,
-        )
-        .unwrap();
+        let (input, text_block) = text_block_node(input, context).unwrap();
 
         assert_eq!(
             text_block,
@@ -73,9 +102,59 @@ mod tests {
 on large teams. It's easy to customize, adapts to any design,
 and the build size is tiny.""#
                 ),
+                span: context.span(2, 173),
             }
         );
 
         assert_eq!(input, "\n    figcaption.font-medium");
     }
+
+    #[test]
+    fn it_should_detect_indentation_of_first_text_block() {
+        let input = ".\n\tline one\n\tline two\nspan\n";
+
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (input, text_block) = text_block_node(input, context).unwrap();
+
+        assert_eq!(text_block.text, "line one\nline two");
+        assert_eq!(context.indent_string.as_deref(), Some("\t"));
+        assert_eq!(input, "\nspan\n");
+    }
+
+    #[test]
+    fn it_should_return_text_node() {
+        let input = "h1 Hello World\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, text) = text_node(&input[2..], &context).unwrap();
+
+        assert_eq!(
+            text,
+            TextNode {
+                text: String::from("Hello World"),
+                span: context.span(3, 14),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn it_should_return_piped_text_node() {
+        let input = "| Click \n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, text) = piped_text_node(input, &context).unwrap();
+
+        assert_eq!(
+            text,
+            TextNode {
+                text: String::from("Click "),
+                span: context.span(0, 8),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
 }
@@ -1,7 +1,8 @@
 use nom::{
     IResult, Parser,
     branch::alt,
-    bytes::complete::{tag, take_until1},
+    bytes::complete::{tag, take_till, take_until1},
+    combinator::opt,
 };
 
 use crate::parser::HsmlProcessContext;
@@ -15,43 +16,42 @@ pub fn process_text_block<'a>(
     // eat one \r\n or \n
     let (rest, _) = alt((tag("\r\n"), tag("\n"))).parse(rest)?;
 
-    let indent_string: &str = if let Some(indent_string) = &context.indent_string {
-        indent_string
-    } else {
-        "  "
-    };
+    // a text block can be the first indented content, then its first line sets the indentation
+    if context.indent_string.is_none() {
+        let first_line = rest.split_once('\n').map_or(rest, |(line, _)| line);
+        let indentation_len = first_line.len() - first_line.trim_start_matches([' ', '\t']).len();
+
+        let level_count = context.indent_level + 1;
+        if indentation_len > 0 && indentation_len % level_count == 0 {
+            context.indent_string = Some(first_line[..indentation_len / level_count].to_string());
+        }
+    }
+
+    let indent_string: &str = context.indent_string.as_deref().unwrap_or("  ");
 
     let indent_string: &str = &indent_string.repeat(context.indent_level + 1);
 
-    let mut text_block_index = 0;
+    // byte offsets of the end of the text block and of the current line
+    let mut text_block_len = 0;
+    let mut line_start = 0;
 
     // loop over each line until we find a line that does not fulfill the indentation
-    for (index, c) in rest.chars().enumerate() {
-        if c == '\n' {
-            // if next char is also a \n, then continue
-            let next_char = rest.chars().nth(index + 1);
-            if next_char == Some('\n') {
-                text_block_index = index + 1;
-                continue;
-            }
+    for line in rest.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
 
-            let line = &rest[index + 1..];
-
-            // otherwise check the indentation and if it does not fulfill the indentation, then break
-            if !line.starts_with(indent_string) {
+        // blank lines only belong to the text block if an indented line follows
+        if !content.trim().is_empty() {
+            if !content.starts_with(indent_string) {
                 break;
             }
-        } else {
-            text_block_index = index;
-            continue;
-        }
-    }
 
-    let text_block = &rest[..text_block_index + 1];
+            text_block_len = line_start + content.len();
+        }
 
-    let rest = &rest[text_block_index + 1..];
+        line_start += line.len();
+    }
 
-    Ok((rest, text_block))
+    Ok((&rest[text_block_len..], &rest[..text_block_len]))
 }
 
 pub fn process_text(input: &str) -> IResult<&str, &str> {
@@ -59,11 +59,21 @@ pub fn process_text(input: &str) -> IResult<&str, &str> {
     take_until1("\n")(input)
 }
 
+pub fn process_piped_text(input: &str) -> IResult<&str, &str> {
+    let (input, _) = tag("|")(input)?;
+
+    // only the first whitespace separates the pipe from the text, all others are part of the text
+    let (input, _) = opt(tag(" ")).parse(input)?;
+
+    // read until end of line
+    take_till(|c| c == '\r' || c == '\n')(input)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::{
         HsmlProcessContext,
-        text::process::{process_text, process_text_block},
+        text::process::{process_piped_text, process_text, process_text_block},
     };
 
     #[test]
@@ -71,6 +81,7 @@ mod tests {
         let mut context = HsmlProcessContext {
             indent_string: Some(String::from("  ")),
             indent_level: 1,
+            ..Default::default()
         };
 
         let input = r#".
@@ -98,6 +109,37 @@ span other text
         );
     }
 
+    #[test]
+    fn it_should_process_non_ascii_text_block() {
+        let mut context = HsmlProcessContext {
+            indent_string: Some(String::from("  ")),
+            indent_level: 1,
+            ..Default::default()
+        };
+
+        let input = ".\n    héllo wörld\n    ünïcode ok\n  span\n";
+
+        let (rest, text_block) = process_text_block(input, &mut context).unwrap();
+
+        assert_eq!(text_block, "    héllo wörld\n    ünïcode ok");
+        assert_eq!(rest, "\n  span\n");
+    }
+
+    #[test]
+    fn it_should_process_empty_text_block() {
+        let (rest, text_block) =
+            process_text_block(".\n", &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(text_block, "");
+        assert_eq!(rest, "");
+
+        let (rest, text_block) =
+            process_text_block(".\nspan\n", &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(text_block, "");
+        assert_eq!(rest, "span\n");
+    }
+
     #[test]
     fn test_process_text() {
         let input = " hello world\n";
@@ -107,4 +149,24 @@ span other text
         assert_eq!(text, "hello world");
         assert_eq!(rest, "\n");
     }
+
+    #[test]
+    fn it_should_process_piped_text() {
+        let input = "|  to continue \nspan\n";
+
+        let (rest, text) = process_piped_text(input).unwrap();
+
+        assert_eq!(text, " to continue ");
+        assert_eq!(rest, "\nspan\n");
+    }
+
+    #[test]
+    fn it_should_process_empty_piped_text() {
+        let input = "|";
+
+        let (rest, text) = process_piped_text(input).unwrap();
+
+        assert_eq!(text, "");
+        assert_eq!(rest, "");
+    }
 }